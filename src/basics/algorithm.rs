@@ -1,15 +1,23 @@
+// numerical algorithms below follow the NURBS Book, index loops are kept as is
+#![allow(clippy::needless_range_loop)]
+
 use crate::{
-    basics::{ControlPoint, HomoControlPoint},
-    basis_function::Basis,
+    basics::{
+        linear::{solve, solve_banded},
+        ControlPoint, HomoControlPoint, InnerProductSpace,
+    },
+    basis_function::{Basis, BasisFunctionError, BsplineBasis, Knots},
 };
+use num::integer::binomial;
+use std::ops::Range;
 
 /// de_casteljaul algorithm
 ///
-pub(crate) fn de_casteljaul<P>(n: usize, u: f64, control_points: &Vec<P>) -> P
+pub(crate) fn de_casteljaul<P>(n: usize, u: f64, control_points: &[P]) -> P
 where
     P: ControlPoint,
 {
-    let mut q = control_points.clone();
+    let mut q = control_points.to_vec();
     let u1 = 1.0 - u;
     for k in 1..=n {
         for i in 0..=(n - k) {
@@ -19,7 +27,56 @@ where
     q[0]
 }
 
-pub(crate) fn get_curve_point<B, P>(basis: &B, control_points: &Vec<P>, u: f64) -> P
+/// subdivide bezier curve at u by de_casteljaul algorithm
+///
+/// output: control points of the left part on \[0, u] and the right part on \[u, 1],
+/// both are reparameterized to \[0, 1]
+pub(crate) fn de_casteljaul_split<P>(n: usize, u: f64, control_points: &[P]) -> (Vec<P>, Vec<P>)
+where
+    P: ControlPoint,
+{
+    let mut q = control_points.to_vec();
+    let mut left = Vec::with_capacity(n + 1);
+    let mut right = Vec::with_capacity(n + 1);
+    let u1 = 1.0 - u;
+    left.push(q[0]);
+    right.push(q[n]);
+    for k in 1..=n {
+        for i in 0..=(n - k) {
+            q[i] = q[i] * u1 + q[i + 1] * u;
+        }
+        left.push(q[0]);
+        right.push(q[n - k]);
+    }
+    right.reverse();
+    (left, right)
+}
+
+/// de_casteljaul algorithm of bezier surface,
+/// control net is reduced in the direction of lower degree first
+pub(crate) fn de_casteljaul_surface<P>(control_points: &[Vec<P>], u: f64, v: f64) -> P
+where
+    P: ControlPoint,
+{
+    let n = control_points.len() - 1;
+    let m = control_points[0].len() - 1;
+    if n <= m {
+        let mut q = vec![P::zeros(); m + 1];
+        for (j, q_j) in q.iter_mut().enumerate() {
+            let column = control_points.iter().map(|row| row[j]).collect::<Vec<_>>();
+            *q_j = de_casteljaul(n, u, &column);
+        }
+        de_casteljaul(m, v, &q)
+    } else {
+        let mut q = vec![P::zeros(); n + 1];
+        for (i, q_i) in q.iter_mut().enumerate() {
+            *q_i = de_casteljaul(m, v, &control_points[i]);
+        }
+        de_casteljaul(n, u, &q)
+    }
+}
+
+pub(crate) fn get_curve_point<B, P>(basis: &B, control_points: &[P], u: f64) -> P
 where
     B: Basis,
     P: ControlPoint,
@@ -38,7 +95,7 @@ where
 pub(crate) fn get_surface_point<B, P>(
     u_basis: &B,
     v_basis: &B,
-    control_points: &Vec<Vec<P>>,
+    control_points: &[Vec<P>],
     u: f64,
     v: f64,
 ) -> P
@@ -71,9 +128,64 @@ where
     res
 }
 
+/// control points of isoparametric curve on constant u of surface
+///
+/// output: Q_j = sum_k N_k,p(u) P_k,j, one point for every column of control net
+///
+/// error if u is outside the domain
+pub(crate) fn get_surface_iso_curve_u<B, P>(
+    u_basis: &B,
+    control_points: &[Vec<P>],
+    u: f64,
+) -> Result<Vec<P>, BasisFunctionError>
+where
+    B: Basis,
+    P: ControlPoint,
+{
+    let p = u_basis.degree();
+    let i = u_basis.try_get_span(u)?;
+    let u_values = u_basis.get_values(u, i);
+    let mut res = vec![P::zeros(); control_points[0].len()];
+    for (k, n) in u_values.iter().enumerate() {
+        for (q, cp) in res.iter_mut().zip(&control_points[i - p + k]) {
+            *q += *cp * *n;
+        }
+    }
+    Ok(res)
+}
+
+/// control points of isoparametric curve on constant v of surface
+///
+/// output: Q_i = sum_l N_l,q(v) P_i,l, one point for every row of control net
+///
+/// error if v is outside the domain
+pub(crate) fn get_surface_iso_curve_v<B, P>(
+    v_basis: &B,
+    control_points: &[Vec<P>],
+    v: f64,
+) -> Result<Vec<P>, BasisFunctionError>
+where
+    B: Basis,
+    P: ControlPoint,
+{
+    let q = v_basis.degree();
+    let j = v_basis.try_get_span(v)?;
+    let v_values = v_basis.get_values(v, j);
+    Ok(control_points
+        .iter()
+        .map(|row| {
+            let mut res = P::zeros();
+            for (l, n) in v_values.iter().enumerate() {
+                res += row[j - q + l] * *n;
+            }
+            res
+        })
+        .collect())
+}
+
 pub(crate) fn get_curve_ders<B, P>(
     basis: &B,
-    control_points: &Vec<P>,
+    control_points: &[P],
     der_upper_bond: usize,
     u: f64,
 ) -> Vec<P>
@@ -102,7 +214,7 @@ where
 pub(crate) fn get_surface_ders<B, P>(
     u_basis: &B,
     v_basis: &B,
-    control_points: &Vec<Vec<P>>,
+    control_points: &[Vec<P>],
     der_upper_bond: usize,
     u: f64,
     v: f64,
@@ -113,7 +225,8 @@ where
 {
     let p = u_basis.degree();
     let q = v_basis.degree();
-    let mut output = vec![vec![P::zeros(); q + 1]; p + 1];
+    // derivatives higher than degree are left as zeros
+    let mut output = vec![vec![P::zeros(); der_upper_bond + 1]; der_upper_bond + 1];
     let u_der_upper_bond = if der_upper_bond <= p {
         der_upper_bond
     } else {
@@ -129,7 +242,7 @@ where
     let u_basis_ders = u_basis.get_ders(u, uspan);
     let v_basis_ders = v_basis.get_ders(v, vspan);
     for k in 0..=u_der_upper_bond {
-        let mut temp = vec![P::zeros(); p + 1];
+        let mut temp = vec![P::zeros(); q + 1];
         for s in 0..=q {
             for r in 0..=p {
                 temp[s] += control_points[uspan - p + r][vspan - q + s] * u_basis_ders[k][r];
@@ -151,7 +264,7 @@ where
 
 pub(crate) fn get_rational_ders<B, P>(
     basis: &B,
-    homo_control_points: &Vec<HomoControlPoint<P>>,
+    homo_control_points: &[HomoControlPoint<P>],
     der_upper_bond: usize,
     u: f64,
 ) -> Vec<P>
@@ -184,9 +297,1922 @@ where
     for k in 0..=du {
         let mut v = a_ders[k];
         for i in 1..=k {
-            v = v - ck[k - i] * (num::integer::binomial::<usize>(k, i) as f64) * w_ders[i];
+            v -= ck[k - i] * (num::integer::binomial::<usize>(k, i) as f64) * w_ders[i];
         }
         ck[k] = v / w_ders[0];
     }
     ck
 }
+
+/// knot insertion, Boehm's algorithm (NURBS Book A5.1)
+///
+/// insert u into knot vector r times, curve shape is unchanged
+///
+/// output:
+/// - new knot vector, m + r + 1 knots
+/// - new control points, n + r + 1 points
+pub(crate) fn curve_knot_ins<P>(
+    degree: usize,
+    knots: &Knots,
+    control_points: &[P],
+    u: f64,
+    r: usize,
+) -> Result<(Knots, Vec<P>), BasisFunctionError>
+where
+    P: ControlPoint,
+{
+    let p = degree;
+    let k = knots.find_span(p, u)?;
+    let s = knots.multiplicity(u);
+    if s + r > p {
+        return Err(BasisFunctionError::TooLargeMultiplicity);
+    }
+    if r == 0 {
+        return Ok((knots.clone(), control_points.to_vec()));
+    }
+    let np = control_points.len() - 1;
+
+    // new knot vector
+    let mut uq = Vec::with_capacity(knots.m() + r + 1);
+    uq.extend_from_slice(&knots.0[..=k]);
+    uq.extend(vec![u; r]);
+    uq.extend_from_slice(&knots.0[k + 1..]);
+
+    // unaffected control points
+    let mut qw = vec![P::zeros(); np + r + 1];
+    qw[..=k - p].copy_from_slice(&control_points[..=k - p]);
+    qw[k - s + r..].copy_from_slice(&control_points[k - s..]);
+    let mut rw = control_points[k - p..=k - s].to_vec();
+
+    // insert the knot r times
+    let mut l = 0;
+    for j in 1..=r {
+        l = k - p + j;
+        for i in 0..=p - j - s {
+            let alpha = (u - knots[l + i]) / (knots[i + k + 1] - knots[l + i]);
+            rw[i] = rw[i + 1] * alpha + rw[i] * (1.0 - alpha);
+        }
+        qw[l] = rw[0];
+        qw[k + r - j - s] = rw[p - j - s];
+    }
+    // remaining control points
+    if l + 1 < k - s {
+        qw[l + 1..k - s].copy_from_slice(&rw[1..k - s - l]);
+    }
+    Ok((Knots::new(uq), qw))
+}
+
+/// knot refinement (NURBS Book A5.4)
+///
+/// insert all knots in x at once, x should be sorted in non-decreasing order
+///
+/// output:
+/// - new knot vector, m + x.len() + 1 knots
+/// - new control points, n + x.len() + 1 points
+pub(crate) fn curve_knot_refine<P>(
+    degree: usize,
+    knots: &Knots,
+    control_points: &[P],
+    x: &[f64],
+) -> Result<(Knots, Vec<P>), BasisFunctionError>
+where
+    P: ControlPoint,
+{
+    if x.is_empty() {
+        return Ok((knots.clone(), control_points.to_vec()));
+    }
+    if x.windows(2).any(|w| w[0] > w[1]) {
+        return Err(BasisFunctionError::NonMonotonicKnots);
+    }
+    let p = degree;
+    let n = control_points.len() - 1;
+    let m = knots.m();
+    let r = x.len() - 1;
+    let a = knots.find_span(p, x[0])?;
+    let b = knots.find_span(p, x[r])? + 1;
+
+    let mut ubar = vec![0.0; m + r + 2];
+    let mut qw = vec![P::zeros(); n + r + 2];
+    qw[..=a - p].copy_from_slice(&control_points[..=a - p]);
+    qw[b + r..].copy_from_slice(&control_points[b - 1..]);
+    ubar[..=a].copy_from_slice(&knots.0[..=a]);
+    ubar[b + p + r + 1..].copy_from_slice(&knots.0[b + p..]);
+
+    let mut i = b + p - 1;
+    let mut k = b + p + r;
+    for j in (0..=r).rev() {
+        while x[j] <= knots[i] && i > a {
+            qw[k - p - 1] = control_points[i - p - 1];
+            ubar[k] = knots[i];
+            k -= 1;
+            i -= 1;
+        }
+        qw[k - p - 1] = qw[k - p];
+        for l in 1..=p {
+            let ind = k - p + l;
+            let alpha = ubar[k + l] - x[j];
+            if alpha.abs() == 0.0 {
+                qw[ind - 1] = qw[ind];
+            } else {
+                let alpha = alpha / (ubar[k + l] - knots[i + l - p]);
+                qw[ind - 1] = qw[ind - 1] * alpha + qw[ind] * (1.0 - alpha);
+            }
+        }
+        ubar[k] = x[j];
+        k -= 1;
+    }
+    Ok((Knots::new(ubar), qw))
+}
+
+/// knot removal (NURBS Book A5.8)
+///
+/// try to remove u from knot vector `num` times, a removal is accepted only if
+/// the deviation of control points is not greater than tolerance
+///
+/// output:
+/// - new knot vector
+/// - new control points
+/// - number of times u was actually removed
+/// - max deviation of accepted removals
+pub(crate) fn curve_knot_remove<P>(
+    degree: usize,
+    knots: &Knots,
+    control_points: &[P],
+    u: f64,
+    num: usize,
+    tolerance: f64,
+) -> Result<(Knots, Vec<P>, usize, f64), BasisFunctionError>
+where
+    P: ControlPoint + InnerProductSpace,
+{
+    let p = degree as isize;
+    let s = knots.multiplicity(u);
+    let r = knots.find_span(degree, u)? as isize;
+    if s == 0 || u <= knots[degree] || u >= knots[knots.m() - degree] {
+        return Err(BasisFunctionError::NotInteriorKnot);
+    }
+    let num = num.min(s);
+    let s = s as isize;
+    let n = control_points.len() as isize - 1;
+    let m = knots.m() as isize;
+    let ord = p + 1;
+    let fout = (2 * r - s - p) / 2;
+    let mut first = r - p;
+    let mut last = r - s;
+
+    let uk = |i: isize| knots[i as usize];
+    let mut pw = control_points.to_vec();
+    let mut temp = vec![P::zeros(); 2 * degree + 1];
+    let mut max_deviation: f64 = 0.0;
+    let mut t = 0;
+    while t < num as isize {
+        // this loop is equation 5.28
+        let off = first - 1;
+        temp[0] = pw[off as usize];
+        temp[(last + 1 - off) as usize] = pw[(last + 1) as usize];
+        let mut i = first;
+        let mut j = last;
+        let mut ii = 1;
+        let mut jj = last - off;
+        while j - i > t {
+            let alfi = (u - uk(i)) / (uk(i + ord + t) - uk(i));
+            let alfj = (u - uk(j - t)) / (uk(j + ord) - uk(j - t));
+            temp[ii as usize] = (pw[i as usize] - temp[(ii - 1) as usize] * (1.0 - alfi)) / alfi;
+            temp[jj as usize] = (pw[j as usize] - temp[(jj + 1) as usize] * alfj) / (1.0 - alfj);
+            i += 1;
+            ii += 1;
+            j -= 1;
+            jj -= 1;
+        }
+        // check if knot is removable
+        let deviation = if j - i < t {
+            temp[(ii - 1) as usize].distance(&temp[(jj + 1) as usize])
+        } else {
+            let alfi = (u - uk(i)) / (uk(i + ord + t) - uk(i));
+            pw[i as usize].distance(
+                &(temp[(ii + t + 1) as usize] * alfi + temp[(ii - 1) as usize] * (1.0 - alfi)),
+            )
+        };
+        if deviation > tolerance {
+            break;
+        }
+        max_deviation = max_deviation.max(deviation);
+        // successful removal, save new control points
+        let mut i = first;
+        let mut j = last;
+        while j - i > t {
+            pw[i as usize] = temp[(i - off) as usize];
+            pw[j as usize] = temp[(j - off) as usize];
+            i += 1;
+            j -= 1;
+        }
+        first -= 1;
+        last += 1;
+        t += 1;
+    }
+    if t == 0 {
+        return Ok((knots.clone(), pw, 0, 0.0));
+    }
+
+    // shift knots
+    let mut uq = knots.0.clone();
+    for k in r + 1..=m {
+        uq[(k - t) as usize] = uq[k as usize];
+    }
+    uq.truncate((m - t + 1) as usize);
+    // pj through pi will be overwritten
+    let mut j = fout;
+    let mut i = j;
+    for k in 1..t {
+        if k % 2 == 1 {
+            i += 1;
+        } else {
+            j -= 1;
+        }
+    }
+    for k in i + 1..=n {
+        pw[j as usize] = pw[k as usize];
+        j += 1;
+    }
+    pw.truncate((n - t + 1) as usize);
+    Ok((Knots::new(uq), pw, t as usize, max_deviation))
+}
+
+/// transpose a control net, rows become columns
+pub(crate) fn transpose<P>(control_points: &[Vec<P>]) -> Vec<Vec<P>>
+where
+    P: ControlPoint,
+{
+    if control_points.is_empty() {
+        return Vec::new();
+    }
+    (0..control_points[0].len())
+        .map(|j| control_points.iter().map(|row| row[j]).collect())
+        .collect()
+}
+
+/// knot refinement of surface in v direction (NURBS Book A5.5),
+/// every row of control net is refined as a curve
+pub(crate) fn surface_knot_refine_v<P>(
+    degree: usize,
+    knots: &Knots,
+    control_points: &[Vec<P>],
+    x: &[f64],
+) -> Result<(Knots, Vec<Vec<P>>), BasisFunctionError>
+where
+    P: ControlPoint,
+{
+    let mut new_knots = knots.clone();
+    let mut new_control_points = Vec::with_capacity(control_points.len());
+    for row in control_points {
+        let (k, cps) = curve_knot_refine(degree, knots, row, x)?;
+        new_knots = k;
+        new_control_points.push(cps);
+    }
+    Ok((new_knots, new_control_points))
+}
+
+/// knot refinement of surface in u direction (NURBS Book A5.5),
+/// every column of control net is refined as a curve
+pub(crate) fn surface_knot_refine_u<P>(
+    degree: usize,
+    knots: &Knots,
+    control_points: &[Vec<P>],
+    x: &[f64],
+) -> Result<(Knots, Vec<Vec<P>>), BasisFunctionError>
+where
+    P: ControlPoint,
+{
+    let (new_knots, columns) = surface_knot_refine_v(degree, knots, &transpose(control_points), x)?;
+    Ok((new_knots, transpose(&columns)))
+}
+
+/// degree elevation of bezier curve
+///
+/// output: control points of elevated curve, degree + t + 1 points
+pub(crate) fn bezier_degree_elevate<P>(control_points: &[P], t: usize) -> Vec<P>
+where
+    P: ControlPoint,
+{
+    let p = control_points.len() - 1;
+    let ph = p + t;
+    (0..=ph)
+        .map(|i| {
+            let inv = 1.0 / binomial(ph, i) as f64;
+            let mut q = P::zeros();
+            for j in i.saturating_sub(t)..=p.min(i) {
+                q += control_points[j] * (inv * (binomial(p, j) * binomial(t, i - j)) as f64);
+            }
+            q
+        })
+        .collect()
+}
+
+/// degree elevation of B-spline curve (NURBS Book A5.9)
+///
+/// raise degree from p to p + t, curve shape is unchanged
+///
+/// output:
+/// - new knot vector
+/// - new control points
+pub(crate) fn curve_degree_elevate<P>(
+    degree: usize,
+    knots: &Knots,
+    control_points: &[P],
+    t: usize,
+) -> (Knots, Vec<P>)
+where
+    P: ControlPoint,
+{
+    if t == 0 {
+        return (knots.clone(), control_points.to_vec());
+    }
+    let p = degree;
+    let n = control_points.len() - 1;
+    let m = n + p + 1;
+    let ph = p + t;
+    let ph2 = ph / 2;
+
+    // bezier degree elevation coefficients
+    let mut bezalfs = vec![vec![0.0; p + 1]; ph + 1];
+    bezalfs[0][0] = 1.0;
+    bezalfs[ph][p] = 1.0;
+    for i in 1..=ph2 {
+        let inv = 1.0 / binomial(ph, i) as f64;
+        for j in i.saturating_sub(t)..=p.min(i) {
+            bezalfs[i][j] = inv * (binomial(p, j) * binomial(t, i - j)) as f64;
+        }
+    }
+    for i in ph2 + 1..ph {
+        for j in i.saturating_sub(t)..=p.min(i) {
+            bezalfs[i][j] = bezalfs[ph - i][p - j];
+        }
+    }
+
+    let mut uh = vec![0.0; (m + 1) * (t + 1) + ph + 1];
+    let mut qw = vec![P::zeros(); (m + 1) * (t + 1)];
+    let mut bpts = control_points[..=p].to_vec();
+    let mut next_bpts = vec![P::zeros(); p.max(1)];
+    let mut ebpts = vec![P::zeros(); ph + 1];
+    let mut alfs = vec![0.0; p.max(1)];
+
+    let mut mh = ph;
+    let mut kind = ph + 1;
+    let mut r: isize = -1;
+    let mut a = p;
+    let mut b = p + 1;
+    let mut cind = 1;
+    let mut ua = knots[0];
+    qw[0] = control_points[0];
+    uh[..=ph].fill(ua);
+
+    while b < m {
+        let i = b;
+        while b < m && knots[b] == knots[b + 1] {
+            b += 1;
+        }
+        let mul = b - i + 1;
+        mh += mul + t;
+        let ub = knots[b];
+        let oldr = r;
+        r = p as isize - mul as isize;
+        // insert knot u(b) r times
+        let lbz = if oldr > 0 { (oldr as usize + 2) / 2 } else { 1 };
+        let rbz = if r > 0 {
+            ph - (r as usize).div_ceil(2)
+        } else {
+            ph
+        };
+        if r > 0 {
+            let numer = ub - ua;
+            for k in (mul + 1..=p).rev() {
+                alfs[k - mul - 1] = numer / (knots[a + k] - ua);
+            }
+            for j in 1..=r as usize {
+                let save = r as usize - j;
+                let s = mul + j;
+                for k in (s..=p).rev() {
+                    bpts[k] = bpts[k] * alfs[k - s] + bpts[k - 1] * (1.0 - alfs[k - s]);
+                }
+                next_bpts[save] = bpts[p];
+            }
+        }
+        // degree elevate bezier, only points lbz..=ph are used below
+        for i in lbz..=ph {
+            ebpts[i] = P::zeros();
+            for j in i.saturating_sub(t)..=p.min(i) {
+                ebpts[i] += bpts[j] * bezalfs[i][j];
+            }
+        }
+        if oldr > 1 {
+            // remove knot u = U[a] oldr times
+            let den = ub - ua;
+            let bet = (ub - uh[kind - 1]) / den;
+            for tr in 1..oldr as usize {
+                let mut i = kind - 1 - tr;
+                let mut j = kind - 1 + tr;
+                let mut kj = j - kind + 1;
+                while j - i > tr {
+                    if i < cind {
+                        let alf = (ub - uh[i]) / (ua - uh[i]);
+                        qw[i] = qw[i] * alf + qw[i - 1] * (1.0 - alf);
+                    }
+                    if j >= lbz {
+                        if j - tr <= kind - ph + oldr as usize {
+                            let gam = (ub - uh[j - tr]) / den;
+                            ebpts[kj] = ebpts[kj] * gam + ebpts[kj + 1] * (1.0 - gam);
+                        } else {
+                            ebpts[kj] = ebpts[kj] * bet + ebpts[kj + 1] * (1.0 - bet);
+                        }
+                    }
+                    i += 1;
+                    j -= 1;
+                    kj = kj.wrapping_sub(1);
+                }
+            }
+        }
+        // load the knot ua
+        if a != p {
+            for _ in 0..(ph as isize - oldr) {
+                uh[kind] = ua;
+                kind += 1;
+            }
+        }
+        // load control points into qw
+        for ebpt in &ebpts[lbz..=rbz] {
+            qw[cind] = *ebpt;
+            cind += 1;
+        }
+        if b < m {
+            // set up for next pass through loop
+            let r = r.max(0) as usize;
+            bpts[..r].copy_from_slice(&next_bpts[..r]);
+            bpts[r..=p].copy_from_slice(&control_points[b - p + r..=b]);
+            a = b;
+            b += 1;
+            ua = ub;
+        } else {
+            // end knot
+            uh[kind..=kind + ph].fill(ub);
+        }
+    }
+    let nh = mh - ph - 1;
+    qw.truncate(nh + 1);
+    uh.truncate(nh + ph + 2);
+    (Knots::new(uh), qw)
+}
+
+/// degree reduction of bezier curve from p to p - 1 (NURBS Book Eq. 5.41 - 5.46)
+///
+/// output:
+/// - control points of reduced curve, p points
+/// - max deviation between reduced and original curve
+pub(crate) fn bezier_degree_reduce<P>(control_points: &[P]) -> (Vec<P>, f64)
+where
+    P: ControlPoint + InnerProductSpace,
+{
+    let p = control_points.len() - 1;
+    let r = (p - 1) / 2;
+    let alpha = |i: usize| i as f64 / p as f64;
+    let mut rbpts = vec![P::zeros(); p];
+    rbpts[0] = control_points[0];
+    rbpts[p - 1] = control_points[p];
+    for i in 1..r {
+        rbpts[i] = (control_points[i] - rbpts[i - 1] * alpha(i)) / (1.0 - alpha(i));
+    }
+    for i in (r + 1..p - 1).rev() {
+        rbpts[i] = (control_points[i + 1] - rbpts[i + 1] * (1.0 - alpha(i + 1))) / alpha(i + 1);
+    }
+    let left = |rbpts: &[P]| {
+        if r == 0 {
+            control_points[0]
+        } else {
+            (control_points[r] - rbpts[r - 1] * alpha(r)) / (1.0 - alpha(r))
+        }
+    };
+    if p % 2 == 0 {
+        rbpts[r] = left(&rbpts);
+        let error = control_points[r + 1].distance(&((rbpts[r] + rbpts[r + 1]) * 0.5));
+        (rbpts, error)
+    } else {
+        let pl = left(&rbpts);
+        let pr = (control_points[r + 1] - rbpts[r + 1] * (1.0 - alpha(r + 1))) / alpha(r + 1);
+        rbpts[r] = (pl + pr) * 0.5;
+        let error = 0.5 * (1.0 - alpha(r)) * pl.distance(&pr);
+        (rbpts, error)
+    }
+}
+
+/// degree reduction of B-spline curve from p to p - 1 (NURBS Book A5.11)
+///
+/// output:
+/// - new knot vector
+/// - new control points
+/// - max deviation bound between reduced and original curve
+pub(crate) fn curve_degree_reduce<P>(
+    degree: usize,
+    knots: &Knots,
+    control_points: &[P],
+) -> Result<(Knots, Vec<P>, f64), BasisFunctionError>
+where
+    P: ControlPoint + InnerProductSpace,
+{
+    let p = degree;
+    if p < 2 {
+        return Err(BasisFunctionError::TooSmallDegree);
+    }
+    let n = control_points.len() - 1;
+    let m = n + p + 1;
+    let ph = p - 1;
+
+    let mut uh = vec![0.0; m + 1];
+    let mut pw = vec![P::zeros(); n + 1];
+    let mut bpts = control_points[..=p].to_vec();
+    let mut next_bpts = vec![P::zeros(); p];
+    let mut alphas = vec![0.0; p];
+    let mut e = vec![0.0; m + 1];
+
+    let mut mh = ph;
+    let mut kind = ph + 1;
+    let mut r: isize = -1;
+    let mut a = p;
+    let mut b = p + 1;
+    let mut cind = 1;
+    pw[0] = control_points[0];
+    uh[..=ph].fill(knots[0]);
+
+    while b < m {
+        let i = b;
+        while b < m && knots[b] == knots[b + 1] {
+            b += 1;
+        }
+        let mult = b - i + 1;
+        mh = mh + mult - 1;
+        let oldr = r;
+        r = p as isize - mult as isize;
+        let lbz = if oldr > 0 { (oldr as usize + 2) / 2 } else { 1 };
+        // insert knot U[b] r times
+        if r > 0 {
+            let numer = knots[b] - knots[a];
+            for k in (mult + 1..=p).rev() {
+                alphas[k - mult - 1] = numer / (knots[a + k] - knots[a]);
+            }
+            for j in 1..=r as usize {
+                let save = r as usize - j;
+                let s = mult + j;
+                for k in (s..=p).rev() {
+                    bpts[k] = bpts[k] * alphas[k - s] + bpts[k - 1] * (1.0 - alphas[k - s]);
+                }
+                next_bpts[save] = bpts[p];
+            }
+        }
+        // degree reduce bezier segment
+        let (mut rbpts, max_err) = bezier_degree_reduce(&bpts);
+        e[a] += max_err;
+        // remove knot U[a] oldr times
+        if oldr > 0 {
+            let oldr = oldr as usize;
+            let mut i = kind;
+            for k in 0..oldr {
+                i = kind - k;
+                let mut j = kind + k;
+                let mut kj = j as isize - kind as isize;
+                while j - i > k {
+                    let alfa = (knots[a] - uh[i - 1]) / (knots[b] - uh[i - 1]);
+                    let beta = (knots[a] - uh[j - k - 1]) / (knots[b] - uh[j - k - 1]);
+                    pw[i - 1] = (pw[i - 1] - pw[i - 2] * (1.0 - alfa)) / alfa;
+                    let kju = kj as usize;
+                    rbpts[kju] = (rbpts[kju] - rbpts[kju + 1] * beta) / (1.0 - beta);
+                    i += 1;
+                    j -= 1;
+                    kj -= 1;
+                }
+                // knot removal error bound
+                let kj1 = (kj + 1) as usize;
+                let br = if j < i + k {
+                    pw[i - 2].distance(&rbpts[kj1])
+                } else {
+                    let delta = (knots[a] - uh[i - 1]) / (knots[b] - uh[i - 1]);
+                    pw[i - 1].distance(&(rbpts[kj1] * delta + pw[i - 2] * (1.0 - delta)))
+                };
+                // update the error vector of affected knot spans
+                let kk = a + oldr - k;
+                let q = (2 * p - k).div_ceil(2);
+                for ii in kk.saturating_sub(q)..=a {
+                    e[ii] += br;
+                }
+            }
+            cind = i - 1;
+        }
+        // load knot vector and control points
+        if a != p {
+            for _ in 0..(ph as isize - oldr) {
+                uh[kind] = knots[a];
+                kind += 1;
+            }
+        }
+        for rbpt in &rbpts[lbz..=ph] {
+            pw[cind] = *rbpt;
+            cind += 1;
+        }
+        if b < m {
+            // set up for next pass through loop
+            let r = r.max(0) as usize;
+            bpts[..r].copy_from_slice(&next_bpts[..r]);
+            bpts[r..=p].copy_from_slice(&control_points[b - p + r..=b]);
+            a = b;
+            b += 1;
+        } else {
+            uh[kind..=kind + ph].fill(knots[b]);
+        }
+    }
+    let nh = mh - ph - 1;
+    pw.truncate(nh + 1);
+    uh.truncate(nh + ph + 2);
+    let max_err = e.iter().fold(0.0f64, |max, &e| max.max(e));
+    Ok((Knots::new(uh), pw, max_err))
+}
+
+/// knot vector and control points of a B-spline curve
+type CurvePart<P> = (Knots, Vec<P>);
+
+/// split B-spline curve at u by inserting u until its multiplicity reaches degree
+///
+/// output: knot vectors and control points of the left part on \[u_p, u] and
+/// the right part on \[u, u_m-p]
+///
+/// error if u is not inside the domain of curve
+pub(crate) fn curve_split<P>(
+    degree: usize,
+    knots: &Knots,
+    control_points: &[P],
+    u: f64,
+) -> Result<(CurvePart<P>, CurvePart<P>), BasisFunctionError>
+where
+    P: ControlPoint,
+{
+    let p = degree;
+    if u <= knots[p] {
+        return Err(BasisFunctionError::ULessThanMin);
+    }
+    if u >= knots[knots.m() - p] {
+        return Err(BasisFunctionError::UGreaterThanMax);
+    }
+    let s = knots.multiplicity(u);
+    let (knots, control_points) = curve_knot_ins(p, knots, control_points, u, p.saturating_sub(s))?;
+    let s = s.max(p);
+    // first index of u in knot vector
+    let a = knots.0.iter().position(|&k| k == u).unwrap();
+
+    let left_knots = [&knots.0[..a], &vec![u; p + 1]].concat();
+    let left_control_points = control_points[..a].to_vec();
+    let right_knots = [&vec![u; p + 1], &knots.0[a + s..]].concat();
+    let right_control_points = control_points[a + s - p - 1..].to_vec();
+    Ok((
+        (Knots::new(left_knots), left_control_points),
+        (Knots::new(right_knots), right_control_points),
+    ))
+}
+
+/// clamp start of curve, the start of domain gets multiplicity p + 1 by knot insertion
+/// and the knots and control points before it are dropped
+fn curve_clamp_start<P>(degree: usize, knots: &Knots, control_points: &[P]) -> CurvePart<P>
+where
+    P: ControlPoint,
+{
+    let p = degree;
+    let u = knots[p];
+    let s = knots.multiplicity(u);
+    // u is in the domain and s + r <= p, insertion can not fail
+    let (knots, control_points) =
+        curve_knot_ins(p, knots, control_points, u, p.saturating_sub(s)).unwrap();
+    // first index and multiplicity of u
+    let a = knots.0.iter().position(|&k| k == u).unwrap();
+    let s = s.max(p);
+    let clamped_knots = [&vec![u; p + 1], &knots.0[a + s..]].concat();
+    (
+        Knots::new(clamped_knots),
+        control_points[a + s - p - 1..].to_vec(),
+    )
+}
+
+/// convert curve on unclamped knots into the same curve on clamped knots,
+/// both ends are clamped by knot insertion, domain is unchanged
+pub(crate) fn curve_clamp<P>(degree: usize, knots: &Knots, control_points: &[P]) -> CurvePart<P>
+where
+    P: ControlPoint,
+{
+    let (knots, control_points) = curve_clamp_start(degree, knots, control_points);
+    // clamp the end as start of the reversed curve
+    let reversed = control_points.iter().rev().copied().collect::<Vec<_>>();
+    let (knots, control_points) = curve_clamp_start(degree, &knots.reverse(degree), &reversed);
+    (
+        knots.reverse(degree),
+        control_points.into_iter().rev().collect(),
+    )
+}
+
+/// convert clamped curve into the same curve on unclamped knots (NURBS Book A12.1),
+/// the new end knots continue the spacing of the knots at the other end,
+/// so a closed curve with C^(p-1) continuity at the seam becomes periodic,
+/// its first p control points equal the last p
+pub(crate) fn curve_unclamp<P>(degree: usize, knots: &Knots, control_points: &[P]) -> CurvePart<P>
+where
+    P: ControlPoint,
+{
+    let p = degree;
+    let n = control_points.len() - 1;
+    let mut u = knots.0.clone();
+    let mut pw = control_points.to_vec();
+    // unclamp left end
+    for i in 0..p.saturating_sub(1) {
+        u[p - i - 1] = u[p - i] - (u[n - i + 1] - u[n - i]);
+        let mut k = p - 1;
+        for j in (0..=i).rev() {
+            let alpha = (u[p] - u[k]) / (u[p + j + 1] - u[k]);
+            pw[j] = (pw[j] - pw[j + 1] * alpha) / (1.0 - alpha);
+            k -= 1;
+        }
+    }
+    u[0] = u[1] - (u[n - p + 2] - u[n - p + 1]);
+    // unclamp right end
+    for i in 0..p.saturating_sub(1) {
+        u[n + i + 2] = u[n + i + 1] + (u[p + i + 1] - u[p + i]);
+        for j in (0..=i).rev() {
+            let alpha = (u[n + 1] - u[n - j]) / (u[n - j + i + 2] - u[n - j]);
+            pw[n - j] = (pw[n - j] - pw[n - j - 1] * (1.0 - alpha)) / alpha;
+        }
+    }
+    u[n + p + 1] = u[n + p] + (u[2 * p] - u[2 * p - 1]);
+    (Knots::new(u), pw)
+}
+
+/// knot vector and control net of a B-spline surface
+type SurfacePart<P> = (Knots, Vec<Vec<P>>);
+
+/// split B-spline surface at v, every row of control net is split as a curve
+///
+/// output: knot vectors and control nets of the part before v and the part after v
+pub(crate) fn surface_split_v<P>(
+    degree: usize,
+    knots: &Knots,
+    control_points: &[Vec<P>],
+    v: f64,
+) -> Result<(SurfacePart<P>, SurfacePart<P>), BasisFunctionError>
+where
+    P: ControlPoint,
+{
+    let mut left = (knots.clone(), Vec::with_capacity(control_points.len()));
+    let mut right = (knots.clone(), Vec::with_capacity(control_points.len()));
+    for row in control_points {
+        let ((left_knots, left_row), (right_knots, right_row)) =
+            curve_split(degree, knots, row, v)?;
+        left.0 = left_knots;
+        left.1.push(left_row);
+        right.0 = right_knots;
+        right.1.push(right_row);
+    }
+    Ok((left, right))
+}
+
+/// split B-spline surface at u, every column of control net is split as a curve
+///
+/// output: knot vectors and control nets of the part before u and the part after u
+pub(crate) fn surface_split_u<P>(
+    degree: usize,
+    knots: &Knots,
+    control_points: &[Vec<P>],
+    u: f64,
+) -> Result<(SurfacePart<P>, SurfacePart<P>), BasisFunctionError>
+where
+    P: ControlPoint,
+{
+    let ((left_knots, left), (right_knots, right)) =
+        surface_split_v(degree, knots, &transpose(control_points), u)?;
+    Ok((
+        (left_knots, transpose(&left)),
+        (right_knots, transpose(&right)),
+    ))
+}
+
+/// clamp B-spline surface in v direction, every row of control net is clamped as a curve
+pub(crate) fn surface_clamp_v<P>(
+    degree: usize,
+    knots: &Knots,
+    control_points: &[Vec<P>],
+) -> SurfacePart<P>
+where
+    P: ControlPoint,
+{
+    let mut new_knots = knots.clone();
+    let mut new_control_points = Vec::with_capacity(control_points.len());
+    for row in control_points {
+        let (k, cps) = curve_clamp(degree, knots, row);
+        new_knots = k;
+        new_control_points.push(cps);
+    }
+    (new_knots, new_control_points)
+}
+
+/// clamp B-spline surface in u direction, every column of control net is clamped as a curve
+pub(crate) fn surface_clamp_u<P>(
+    degree: usize,
+    knots: &Knots,
+    control_points: &[Vec<P>],
+) -> SurfacePart<P>
+where
+    P: ControlPoint,
+{
+    let (new_knots, columns) = surface_clamp_v(degree, knots, &transpose(control_points));
+    (new_knots, transpose(&columns))
+}
+
+/// split bezier surface at v, every row of control net is split as a curve
+pub(crate) fn bezier_surface_split_v<P>(
+    control_points: &[Vec<P>],
+    v: f64,
+) -> (Vec<Vec<P>>, Vec<Vec<P>>)
+where
+    P: ControlPoint,
+{
+    control_points
+        .iter()
+        .map(|row| de_casteljaul_split(row.len() - 1, v, row))
+        .unzip()
+}
+
+/// split bezier surface at u, every column of control net is split as a curve
+pub(crate) fn bezier_surface_split_u<P>(
+    control_points: &[Vec<P>],
+    u: f64,
+) -> (Vec<Vec<P>>, Vec<Vec<P>>)
+where
+    P: ControlPoint,
+{
+    let (left, right) = bezier_surface_split_v(&transpose(control_points), u);
+    (transpose(&left), transpose(&right))
+}
+
+/// check that interval is not empty and lies in \[start, end]
+pub(crate) fn check_interval(
+    interval: &Range<f64>,
+    start: f64,
+    end: f64,
+) -> Result<(), BasisFunctionError> {
+    if interval.end <= interval.start {
+        return Err(BasisFunctionError::EmptyDomain);
+    }
+    if interval.start < start {
+        return Err(BasisFunctionError::ULessThanMin);
+    }
+    if interval.end > end {
+        return Err(BasisFunctionError::UGreaterThanMax);
+    }
+    Ok(())
+}
+
+/// decompose B-spline curve into bezier segments (NURBS Book A5.6)
+///
+/// output: control points of every bezier segment, p + 1 points each
+pub(crate) fn curve_decompose<P>(degree: usize, knots: &Knots, control_points: &[P]) -> Vec<Vec<P>>
+where
+    P: ControlPoint,
+{
+    let p = degree;
+    let m = knots.m();
+    let mut a = p;
+    let mut b = p + 1;
+    let mut alphas = vec![0.0; p.max(1)];
+    let mut segments = vec![control_points[..=p].to_vec()];
+    while b < m {
+        let i = b;
+        while b < m && knots[b + 1] == knots[b] {
+            b += 1;
+        }
+        let mult = b - i + 1;
+        let nb = segments.len() - 1;
+        if b < m {
+            segments.push(vec![P::zeros(); p + 1]);
+        }
+        if mult < p {
+            let numer = knots[b] - knots[a];
+            for j in (mult + 1..=p).rev() {
+                alphas[j - mult - 1] = numer / (knots[a + j] - knots[a]);
+            }
+            let r = p - mult;
+            // insert knot r times
+            for j in 1..=r {
+                let save = r - j;
+                let s = mult + j;
+                for k in (s..=p).rev() {
+                    let alpha = alphas[k - s];
+                    segments[nb][k] = segments[nb][k] * alpha + segments[nb][k - 1] * (1.0 - alpha);
+                }
+                if b < m {
+                    segments[nb + 1][save] = segments[nb][p];
+                }
+            }
+        }
+        if b < m {
+            // initialize next segment
+            let start = p.saturating_sub(mult);
+            segments[nb + 1][start..].copy_from_slice(&control_points[b - p + start..=b]);
+            a = b;
+            b += 1;
+        }
+    }
+    segments
+}
+
+/// decompose B-spline surface into bezier patches (NURBS Book A5.7)
+///
+/// output: control net of every bezier patch, indexed by \[u segment]\[v segment]
+pub(crate) fn surface_decompose<P>(
+    u_degree: usize,
+    u_knots: &Knots,
+    v_degree: usize,
+    v_knots: &Knots,
+    control_points: &[Vec<P>],
+) -> Vec<Vec<Vec<Vec<P>>>>
+where
+    P: ControlPoint,
+{
+    // decompose in u direction, every column is a curve
+    let columns = transpose(control_points)
+        .iter()
+        .map(|column| curve_decompose(u_degree, u_knots, column))
+        .collect::<Vec<_>>();
+    let strips = (0..columns[0].len())
+        .map(|k| {
+            transpose(
+                &columns
+                    .iter()
+                    .map(|segments| segments[k].clone())
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect::<Vec<_>>();
+    // decompose every strip in v direction, every row is a curve
+    strips
+        .iter()
+        .map(|strip| {
+            let rows = strip
+                .iter()
+                .map(|row| curve_decompose(v_degree, v_knots, row))
+                .collect::<Vec<_>>();
+            (0..rows[0].len())
+                .map(|l| rows.iter().map(|segments| segments[l].clone()).collect())
+                .collect()
+        })
+        .collect()
+}
+
+/// degree elevation of bezier surface in v direction,
+/// every row of control net is elevated as a curve
+pub(crate) fn bezier_surface_degree_elevate_v<P>(control_points: &[Vec<P>], t: usize) -> Vec<Vec<P>>
+where
+    P: ControlPoint,
+{
+    control_points
+        .iter()
+        .map(|row| bezier_degree_elevate(row, t))
+        .collect()
+}
+
+/// degree elevation of bezier surface in u direction,
+/// every column of control net is elevated as a curve
+pub(crate) fn bezier_surface_degree_elevate_u<P>(control_points: &[Vec<P>], t: usize) -> Vec<Vec<P>>
+where
+    P: ControlPoint,
+{
+    transpose(&bezier_surface_degree_elevate_v(
+        &transpose(control_points),
+        t,
+    ))
+}
+
+/// degree elevation of surface in v direction (NURBS Book A5.10),
+/// every row of control net is elevated as a curve
+pub(crate) fn surface_degree_elevate_v<P>(
+    degree: usize,
+    knots: &Knots,
+    control_points: &[Vec<P>],
+    t: usize,
+) -> (Knots, Vec<Vec<P>>)
+where
+    P: ControlPoint,
+{
+    let mut new_knots = knots.clone();
+    let mut new_control_points = Vec::with_capacity(control_points.len());
+    for row in control_points {
+        let (k, cps) = curve_degree_elevate(degree, knots, row, t);
+        new_knots = k;
+        new_control_points.push(cps);
+    }
+    (new_knots, new_control_points)
+}
+
+/// degree elevation of surface in u direction (NURBS Book A5.10),
+/// every column of control net is elevated as a curve
+pub(crate) fn surface_degree_elevate_u<P>(
+    degree: usize,
+    knots: &Knots,
+    control_points: &[Vec<P>],
+    t: usize,
+) -> (Knots, Vec<Vec<P>>)
+where
+    P: ControlPoint,
+{
+    let (new_knots, columns) =
+        surface_degree_elevate_v(degree, knots, &transpose(control_points), t);
+    (new_knots, transpose(&columns))
+}
+
+/// global curve interpolation (NURBS Book A9.1), knots are averages of p successive params
+/// (Eq 9.8), with end derivatives two more control points are added (Eq 9.21, 9.22)
+///
+/// output: knots and control points of curve passing through points at params
+pub(crate) fn curve_interpolate<P>(
+    degree: usize,
+    points: &[P],
+    params: &[f64],
+    end_derivatives: Option<(P, P)>,
+) -> Result<(Knots, Vec<P>), BasisFunctionError>
+where
+    P: ControlPoint,
+{
+    let p = degree;
+    // the two derivative rows of degree 1 are parallel to the point rows
+    if p == 0 || p == 1 && end_derivatives.is_some() {
+        return Err(BasisFunctionError::TooSmallDegree);
+    }
+    if points.len() < 2 {
+        return Err(BasisFunctionError::TooLargeDegree);
+    }
+    let n = points.len() - 1;
+    let average = |j: usize| params[j..j + p].iter().sum::<f64>() / p as f64;
+    let interior = match end_derivatives {
+        None if n >= p => (1..=n - p).map(average).collect::<Vec<_>>(),
+        Some(_) if n + 2 >= p => (0..n + 2 - p).map(average).collect::<Vec<_>>(),
+        _ => return Err(BasisFunctionError::TooLargeDegree),
+    };
+    let knots = Knots::new([vec![params[0]; p + 1], interior, vec![params[n]; p + 1]].concat());
+    let n_ctrl = knots.m() - p;
+    let basis = BsplineBasis::new(p, knots);
+
+    let mut a = Vec::with_capacity(n_ctrl);
+    let mut b = Vec::with_capacity(n_ctrl);
+    for (k, (&u, &q)) in params.iter().zip(points).enumerate() {
+        let span = basis.knots().find_span(p, u)?;
+        let mut row = vec![0.0; n_ctrl];
+        for (j, value) in basis.get_values(u, span).into_iter().enumerate() {
+            row[span - p + j] = value;
+        }
+        a.push(row);
+        b.push(q);
+        if let Some((d0, dn)) = end_derivatives {
+            let knots = basis.knots();
+            if k == 0 {
+                // P_1 - P_0 = u_p+1 / p * D_0
+                let mut row = vec![0.0; n_ctrl];
+                let c = p as f64 / (knots[p + 1] - knots[p]);
+                row[0] = -c;
+                row[1] = c;
+                a.push(row);
+                b.push(d0);
+            }
+            if k + 1 == n {
+                // P_n+2 - P_n+1 = (1 - u_m-p-1) / p * D_n
+                let mut row = vec![0.0; n_ctrl];
+                let m = knots.m();
+                let c = p as f64 / (knots[m - p] - knots[m - p - 1]);
+                row[n_ctrl - 2] = -c;
+                row[n_ctrl - 1] = c;
+                a.push(row);
+                b.push(dn);
+            }
+        }
+    }
+    let control_points = solve(a, b)?;
+    Ok((basis.knots().clone(), control_points))
+}
+
+/// check that there is one parameter for every point, parameters are finite,
+/// non-decreasing and not all equal
+pub(crate) fn check_params(params: &[f64], n_points: usize) -> Result<(), BasisFunctionError> {
+    if params.len() != n_points {
+        return Err(BasisFunctionError::LengthMismatch);
+    }
+    if let Some(u) = params.iter().find(|u| !u.is_finite()) {
+        return Err(if u.is_nan() {
+            BasisFunctionError::UIsNon
+        } else {
+            BasisFunctionError::UIsInfinite
+        });
+    }
+    if params.windows(2).any(|w| w[1] < w[0]) {
+        return Err(BasisFunctionError::NonMonotonicParams);
+    }
+    match (params.first(), params.last()) {
+        (Some(first), Some(last)) if first < last => Ok(()),
+        _ => Err(BasisFunctionError::EmptyDomain),
+    }
+}
+
+/// least squares curve approximation with fixed end points (NURBS Book A9.7),
+/// knots are placed by Eq 9.68, 9.69 so that every knot span contains params
+///
+/// weights: weight of every point in the sum of squared distances, 1 if not given
+///
+/// output: knots and n_ctrl control points, the first and last are the end points
+///
+/// error if params or weights are invalid, see `check_params`
+pub(crate) fn curve_fit_least_squares<P>(
+    degree: usize,
+    points: &[P],
+    params: &[f64],
+    weights: Option<&[f64]>,
+    n_ctrl: usize,
+) -> Result<(Knots, Vec<P>), BasisFunctionError>
+where
+    P: ControlPoint,
+{
+    let p = degree;
+    if p == 0 {
+        return Err(BasisFunctionError::TooSmallDegree);
+    }
+    if n_ctrl < p + 1 {
+        return Err(BasisFunctionError::TooLargeDegree);
+    }
+    if points.len() < n_ctrl {
+        return Err(BasisFunctionError::TooFewPoints);
+    }
+    check_params(params, points.len())?;
+    if weights.is_some_and(|weights| weights.len() != points.len()) {
+        return Err(BasisFunctionError::LengthMismatch);
+    }
+    let m = points.len() - 1;
+    let n = n_ctrl - 1;
+    let d = (m + 1) as f64 / (n - p + 1) as f64;
+    let interior = (1..=n - p).map(|j| {
+        let jd = j as f64 * d;
+        let i = jd as usize;
+        let alpha = jd - i as f64;
+        (1.0 - alpha) * params[i - 1] + alpha * params[i]
+    });
+    let knots = Knots::new(
+        [
+            vec![params[0]; p + 1],
+            interior.collect(),
+            vec![params[m]; p + 1],
+        ]
+        .concat(),
+    );
+    let basis = BsplineBasis::new(p, knots);
+    let control_points = curve_fit_least_squares_on(&basis, points, params, weights)?;
+    Ok((basis.knots().clone(), control_points))
+}
+
+/// least squares curve approximation with fixed end points on given basis
+///
+/// output: control points, the first and last are the end points
+pub(crate) fn curve_fit_least_squares_on<P>(
+    basis: &BsplineBasis,
+    points: &[P],
+    params: &[f64],
+    weights: Option<&[f64]>,
+) -> Result<Vec<P>, BasisFunctionError>
+where
+    P: ControlPoint,
+{
+    let p = basis.degree();
+    let m = points.len() - 1;
+    let n = basis.knots().m() - p - 1;
+    let (q0, qm) = (points[0], points[m]);
+    if n == 1 {
+        return Ok(vec![q0, qm]);
+    }
+
+    // normal equations (N^T W N) P = N^T W R of inner control points P_1 ... P_n-1
+    let mut band = vec![vec![0.0; 2 * p + 1]; n - 1];
+    let mut rhs = vec![P::zeros(); n - 1];
+    for k in 1..m {
+        let u = params[k];
+        let w = weights.map_or(1.0, |weights| weights[k]);
+        let span = basis.knots().find_span(p, u)?;
+        let values = basis.get_values(u, span);
+        // R_k = Q_k - N_0,p(u_k) Q_0 - N_n,p(u_k) Q_m
+        let mut r = points[k];
+        for (a, &value) in values.iter().enumerate() {
+            match span - p + a {
+                0 => r -= q0 * value,
+                i if i == n => r -= qm * value,
+                _ => {}
+            }
+        }
+        for (a, &value_a) in values.iter().enumerate() {
+            let i = span - p + a;
+            if i == 0 || i == n {
+                continue;
+            }
+            for (b, &value_b) in values.iter().enumerate() {
+                let j = span - p + b;
+                if j == 0 || j == n {
+                    continue;
+                }
+                band[i - 1][j + p - i] += w * value_a * value_b;
+            }
+            rhs[i - 1] += r * (w * value_a);
+        }
+    }
+    let inner = solve_banded(band, p, rhs)?;
+    Ok([vec![q0], inner, vec![qm]].concat())
+}
+
+/// error-bounded curve approximation, starts from a single bezier segment fitted by
+/// least squares and splits every knot span in which a point deviates more than eps,
+/// a span is split at the median of its params
+///
+/// output: knots, control points and max deviation of points at their params
+///
+/// error if params are invalid, see `check_params`, or with max deviation
+/// if spans can not be split anymore
+pub(crate) fn curve_approximate<P>(
+    degree: usize,
+    points: &[P],
+    params: &[f64],
+    eps: f64,
+) -> Result<(Knots, Vec<P>, f64), BasisFunctionError>
+where
+    P: ControlPoint + InnerProductSpace,
+{
+    let p = degree;
+    if p == 0 {
+        return Err(BasisFunctionError::TooSmallDegree);
+    }
+    if points.len() < p + 1 {
+        return Err(BasisFunctionError::TooFewPoints);
+    }
+    check_params(params, points.len())?;
+    let m = points.len() - 1;
+    let (start, end) = (params[0], params[m]);
+    let mut interior = Vec::new();
+    // max deviation of the previous fit
+    let mut previous = None;
+    loop {
+        let knots = Knots::new([vec![start; p + 1], interior.clone(), vec![end; p + 1]].concat());
+        let basis = BsplineBasis::new(p, knots);
+        // too many knots for params, the previous fit is the best one
+        let control_points = match (
+            curve_fit_least_squares_on(&basis, points, params, None),
+            previous,
+        ) {
+            (Err(BasisFunctionError::SingularMatrix), Some(deviation)) => {
+                return Err(BasisFunctionError::ToleranceNotReached(deviation))
+            }
+            (result, _) => result?,
+        };
+        let deviations = params
+            .iter()
+            .zip(points)
+            .map(|(&u, q)| get_curve_point(&basis, &control_points, u).distance(q))
+            .collect::<Vec<_>>();
+        let max_deviation = deviations.iter().fold(0.0f64, |a, &b| a.max(b));
+        if max_deviation <= eps {
+            return Ok((basis.knots().clone(), control_points, max_deviation));
+        }
+
+        let breaks = [vec![start], interior.clone(), vec![end]].concat();
+        let spans = breaks
+            .windows(2)
+            .enumerate()
+            .map(|(s, w)| {
+                let last = s + 2 == breaks.len();
+                (0..=m)
+                    .filter(|&k| {
+                        params[k] >= w[0] && (params[k] < w[1] || last && params[k] == w[1])
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        // spans to split, the nearest splittable spans on both sides are split instead
+        // if a span has too few params
+        let splittable = |s: &usize| spans[*s].len() >= 2;
+        let mut to_split = vec![false; spans.len()];
+        for (s, in_span) in spans.iter().enumerate() {
+            if in_span.iter().all(|&k| deviations[k] <= eps) {
+                continue;
+            }
+            if splittable(&s) {
+                to_split[s] = true;
+                continue;
+            }
+            if let Some(l) = (0..s).rev().find(splittable) {
+                to_split[l] = true;
+            }
+            if let Some(r) = (s + 1..spans.len()).find(splittable) {
+                to_split[r] = true;
+            }
+        }
+        let mut new_knots = Vec::new();
+        for (s, in_span) in spans.iter().enumerate() {
+            if !to_split[s] || in_span.len() < 2 {
+                continue;
+            }
+            let h = in_span.len() / 2;
+            let knot = (params[in_span[h - 1]] + params[in_span[h]]) / 2.0;
+            if knot > params[in_span[0]] && knot > breaks[s] && knot < breaks[s + 1] {
+                new_knots.push(knot);
+            }
+        }
+        if new_knots.is_empty() {
+            return Err(BasisFunctionError::ToleranceNotReached(max_deviation));
+        }
+        previous = Some(max_deviation);
+        interior.extend(new_knots);
+        interior.sort_by(f64::total_cmp);
+    }
+}
+
+/// u knots, v knots and control net of a B-spline surface
+type SurfaceKnotsNet<P> = (Knots, Knots, Vec<Vec<P>>);
+
+/// global surface interpolation (NURBS Book A9.4), every column of points is
+/// interpolated in u direction, then every row of resulting points in v direction
+///
+/// output: u knots, v knots and control net of surface passing through points\[k]\[l]
+/// at (u_params\[k], v_params\[l])
+pub(crate) fn surface_interpolate<P>(
+    u_degree: usize,
+    v_degree: usize,
+    points: &[Vec<P>],
+    u_params: &[f64],
+    v_params: &[f64],
+) -> Result<SurfaceKnotsNet<P>, BasisFunctionError>
+where
+    P: ControlPoint,
+{
+    if points.is_empty() || points[0].is_empty() {
+        return Err(BasisFunctionError::TooFewPoints);
+    }
+    let mut u_knots = None;
+    let mut columns = Vec::with_capacity(points[0].len());
+    for column in transpose(points) {
+        let (knots, r) = curve_interpolate(u_degree, &column, u_params, None)?;
+        u_knots = Some(knots);
+        columns.push(r);
+    }
+    let mut v_knots = None;
+    let mut control_points = Vec::with_capacity(points.len());
+    for row in transpose(&columns) {
+        let (knots, row) = curve_interpolate(v_degree, &row, v_params, None)?;
+        v_knots = Some(knots);
+        control_points.push(row);
+    }
+    Ok((u_knots.unwrap(), v_knots.unwrap(), control_points))
+}
+
+/// least squares surface approximation of a grid of points (NURBS Book 9.4.3),
+/// every column of points is fitted in u direction, then every row of resulting
+/// points in v direction, corner points are kept
+///
+/// output: u knots, v knots and n_u * n_v control net
+pub(crate) fn surface_fit_least_squares<P>(
+    u_degree: usize,
+    v_degree: usize,
+    points: &[Vec<P>],
+    u_params: &[f64],
+    v_params: &[f64],
+    n_u: usize,
+    n_v: usize,
+) -> Result<SurfaceKnotsNet<P>, BasisFunctionError>
+where
+    P: ControlPoint,
+{
+    if points.is_empty() || points[0].is_empty() {
+        return Err(BasisFunctionError::TooFewPoints);
+    }
+    let mut u_knots = None;
+    let mut columns = Vec::with_capacity(points[0].len());
+    for column in transpose(points) {
+        let (knots, r) = curve_fit_least_squares(u_degree, &column, u_params, None, n_u)?;
+        u_knots = Some(knots);
+        columns.push(r);
+    }
+    let mut v_knots = None;
+    let mut control_points = Vec::with_capacity(n_u);
+    for row in transpose(&columns) {
+        let (knots, row) = curve_fit_least_squares(v_degree, &row, v_params, None, n_v)?;
+        v_knots = Some(knots);
+        control_points.push(row);
+    }
+    Ok((u_knots.unwrap(), v_knots.unwrap(), control_points))
+}
+
+/// nodes and weights of n-point gauss-legendre quadrature on \[-1, 1]
+pub(crate) fn gauss_legendre(n: usize) -> Vec<(f64, f64)> {
+    (0..n)
+        .map(|i| {
+            let mut x = (std::f64::consts::PI * (i as f64 + 0.75) / (n as f64 + 0.5)).cos();
+            let mut dp = 1.0;
+            for _ in 0..100 {
+                // legendre polynomial P_n(x) by recurrence
+                let (mut p0, mut p1) = (1.0, x);
+                for k in 2..=n {
+                    let k = k as f64;
+                    (p0, p1) = (p1, ((2.0 * k - 1.0) * x * p1 - (k - 1.0) * p0) / k);
+                }
+                dp = n as f64 * (x * p1 - p0) / (x * x - 1.0);
+                let dx = p1 / dp;
+                x -= dx;
+                if dx.abs() < 1e-15 {
+                    break;
+                }
+            }
+            (x, 2.0 / ((1.0 - x * x) * dp * dp))
+        })
+        .collect()
+}
+
+/// gram matrices G_r\[i]\[k] = integral of N_i,p^(r) * N_k,p^(r) over domain for r = 0, 1, 2
+pub(crate) fn basis_gram_matrices(basis: &BsplineBasis) -> [Vec<Vec<f64>>; 3] {
+    let p = basis.degree();
+    let knots = basis.knots();
+    let n = knots.m() - p;
+    let mut gram = [
+        vec![vec![0.0; n]; n],
+        vec![vec![0.0; n]; n],
+        vec![vec![0.0; n]; n],
+    ];
+    let nodes = gauss_legendre(p + 1);
+    for span in p..n {
+        let (a, b) = (knots[span], knots[span + 1]);
+        if a == b {
+            continue;
+        }
+        let half = (b - a) / 2.0;
+        for &(x, w) in &nodes {
+            let ders = basis.get_ders(a + half * (x + 1.0), span);
+            for (r, g) in gram.iter_mut().enumerate().take(p + 1) {
+                for i in 0..=p {
+                    for k in 0..=p {
+                        g[span - p + i][span - p + k] += w * half * ders[r][i] * ders[r][k];
+                    }
+                }
+            }
+        }
+    }
+    gram
+}
+
+/// least squares fitting of scattered points on given bases with thin plate smoothing,
+/// minimize sum |S(u_k, v_k) - Q_k|^2 + smoothing * integral of |S_uu|^2 + 2|S_uv|^2 + |S_vv|^2
+///
+/// unknowns P_i,j are ordered by i * n_v + j, normal equations have half bandwidth p * n_v + q
+pub(crate) fn surface_fit_scattered<P>(
+    u_basis: &BsplineBasis,
+    v_basis: &BsplineBasis,
+    points: &[P],
+    params: &[(f64, f64)],
+    smoothing: f64,
+) -> Result<Vec<Vec<P>>, BasisFunctionError>
+where
+    P: ControlPoint,
+{
+    let (p, q) = (u_basis.degree(), v_basis.degree());
+    let n_u = u_basis.knots().m() - p;
+    let n_v = v_basis.knots().m() - q;
+    let w = p * n_v + q;
+    let mut band = vec![vec![0.0; 2 * w + 1]; n_u * n_v];
+    let mut rhs = vec![P::zeros(); n_u * n_v];
+    for (&point, &(u, v)) in points.iter().zip(params) {
+        let span_u = u_basis.knots().find_span(p, u)?;
+        let span_v = v_basis.knots().find_span(q, v)?;
+        let u_values = u_basis.get_values(u, span_u);
+        let v_values = v_basis.get_values(v, span_v);
+        let entries = (0..=p)
+            .flat_map(|a| (0..=q).map(move |b| (a, b)))
+            .map(|(a, b)| {
+                (
+                    (span_u - p + a) * n_v + span_v - q + b,
+                    u_values[a] * v_values[b],
+                )
+            })
+            .collect::<Vec<_>>();
+        for &(r, value_r) in &entries {
+            rhs[r] += point * value_r;
+            for &(c, value_c) in &entries {
+                band[r][c + w - r] += value_r * value_c;
+            }
+        }
+    }
+    if smoothing > 0.0 {
+        let [gu0, gu1, gu2] = basis_gram_matrices(u_basis);
+        let [gv0, gv1, gv2] = basis_gram_matrices(v_basis);
+        for i in 0..n_u {
+            for k in i.saturating_sub(p)..n_u.min(i + p + 1) {
+                for j in 0..n_v {
+                    for l in j.saturating_sub(q)..n_v.min(j + q + 1) {
+                        let (r, c) = (i * n_v + j, k * n_v + l);
+                        band[r][c + w - r] += smoothing
+                            * (gu2[i][k] * gv0[j][l]
+                                + 2.0 * gu1[i][k] * gv1[j][l]
+                                + gu0[i][k] * gv2[j][l]);
+                    }
+                }
+            }
+        }
+    }
+    let solution = solve_banded(band, w, rhs)?;
+    Ok(solution.chunks(n_v).map(|row| row.to_vec()).collect())
+}
+
+/// clamped uniform basis of n functions on \[start, end]
+pub(crate) fn uniform_basis(
+    degree: usize,
+    n: usize,
+    start: f64,
+    end: f64,
+) -> Result<BsplineBasis, BasisFunctionError> {
+    let basis = BsplineBasis::new_uniform(degree, n)?;
+    let knots = basis.knots().0.iter().map(|k| start + k * (end - start));
+    Ok(BsplineBasis::new(degree, Knots::new(knots.collect())))
+}
+
+/// newton iteration stops if point or step is closer than this
+const INVERSION_POINT_TOLERANCE: f64 = 1e-12;
+/// newton iteration stops if cosine between derivative and C(u) - P is less than this
+const INVERSION_COSINE_TOLERANCE: f64 = 1e-12;
+const INVERSION_MAX_ITERATIONS: usize = 100;
+
+/// parameters sampled evenly in every non-empty knot span of domain, 2 * (p + 1) per span
+pub(crate) fn sample_params<B>(basis: &B) -> Vec<f64>
+where
+    B: Basis,
+{
+    let p = basis.degree();
+    let knots = basis.knots();
+    let n = 2 * (p + 1);
+    let mut params = Vec::new();
+    for span in p..knots.m() - p {
+        let (a, b) = (knots[span], knots[span + 1]);
+        if a < b {
+            params.extend((0..n).map(|i| a + (b - a) * i as f64 / n as f64));
+        }
+    }
+    params.push(knots[knots.m() - p]);
+    params
+}
+
+/// point inversion on curve (NURBS Book 6.1), the nearest of sampled points is refined
+/// by newton iteration, u is clamped to the domain
+///
+/// point: point of curve at u, used for sampling
+///
+/// ders: point and derivatives of curve at u, second derivative is taken as 0 if missing,
+/// the curve is taken as constant on every span if first derivative is missing
+///
+/// output: (u, point, distance)
+pub(crate) fn curve_closest_point<B, P, F, D>(
+    basis: &B,
+    target: &P,
+    point: F,
+    ders: D,
+) -> (f64, P, f64)
+where
+    B: Basis,
+    P: ControlPoint + InnerProductSpace,
+    F: Fn(f64) -> P,
+    D: Fn(f64) -> Vec<P>,
+{
+    let knots = basis.knots();
+    let (start, end) = (knots[basis.degree()], knots[knots.m() - basis.degree()]);
+    let (mut u, _) = sample_params(basis)
+        .into_iter()
+        .map(|u| (u, point(u).distance(target)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap();
+    for _ in 0..INVERSION_MAX_ITERATIONS {
+        let d = ders(u);
+        let Some(&c1) = d.get(1) else {
+            break;
+        };
+        let c2 = d.get(2).copied().unwrap_or_else(P::zeros);
+        let diff = d[0] - *target;
+        if diff.norm() < INVERSION_POINT_TOLERANCE
+            || c1.dot(&diff).abs() < INVERSION_COSINE_TOLERANCE * c1.norm() * diff.norm()
+        {
+            break;
+        }
+        let denominator = c2.dot(&diff) + c1.dot(&c1);
+        if denominator == 0.0 {
+            break;
+        }
+        let next = (u - c1.dot(&diff) / denominator).clamp(start, end);
+        let step = ((next - u) * c1.norm()).abs();
+        u = next;
+        if step < INVERSION_POINT_TOLERANCE {
+            break;
+        }
+    }
+    let point = point(u);
+    (u, point, point.distance(target))
+}
+
+/// point inversion on surface (NURBS Book 6.1), the nearest of grid sampled points is refined
+/// by 2D newton iteration, (u, v) is clamped to the domain
+///
+/// a parameter on boundary is pinned while the distance decreases outward (KKT condition),
+/// then the other one is refined alone. A step must not increase the distance, newton step
+/// falls back to gauss-newton step and scaled gradient step, each is halved until it does not
+///
+/// point: point of surface at (u, v), used for sampling and line search
+///
+/// ders: SKL\[k]\[l] of surface at (u, v) for k + l <= 2
+///
+/// output: (u, v, point, distance)
+///
+/// error if newton iteration does not converge
+pub(crate) fn surface_closest_point<B, P, F, D>(
+    u_basis: &B,
+    v_basis: &B,
+    target: &P,
+    point: F,
+    ders: D,
+) -> Result<(f64, f64, P, f64), BasisFunctionError>
+where
+    B: Basis,
+    P: ControlPoint + InnerProductSpace,
+    F: Fn(f64, f64) -> P,
+    D: Fn(f64, f64) -> Vec<Vec<P>>,
+{
+    let domain = |basis: &B| {
+        let knots = basis.knots();
+        (knots[basis.degree()], knots[knots.m() - basis.degree()])
+    };
+    let (u_start, u_end) = domain(u_basis);
+    let (v_start, v_end) = domain(v_basis);
+    let v_samples = sample_params(v_basis);
+    let (mut u, mut v, _) = sample_params(u_basis)
+        .into_iter()
+        .flat_map(|u| v_samples.iter().map(move |&v| (u, v)))
+        .map(|(u, v)| (u, v, point(u, v).distance(target)))
+        .min_by(|a, b| a.2.total_cmp(&b.2))
+        .unwrap();
+    for _ in 0..INVERSION_MAX_ITERATIONS {
+        let s = ders(u, v);
+        let (su, sv) = (s[1][0], s[0][1]);
+        let r = s[0][0] - *target;
+        let distance = r.norm();
+        let (f, g) = (r.dot(&su), r.dot(&sv));
+        let u_pinned = u == u_start && f > 0.0 || u == u_end && f < 0.0;
+        let v_pinned = v == v_start && g > 0.0 || v == v_end && g < 0.0;
+        if distance < INVERSION_POINT_TOLERANCE
+            || (u_pinned || f.abs() <= INVERSION_COSINE_TOLERANCE * su.norm() * distance)
+                && (v_pinned || g.abs() <= INVERSION_COSINE_TOLERANCE * sv.norm() * distance)
+        {
+            return Ok((u, v, s[0][0], distance));
+        }
+        // step solving [[a, b], [b, c]] (du, dv) = -(f, g) in the free parameters,
+        // none if the matrix is not positive definite there
+        let solve = |a: f64, b: f64, c: f64| match (u_pinned, v_pinned) {
+            (false, false) if a > 0.0 && a * c - b * b > 0.0 => {
+                let det = a * c - b * b;
+                Some(((b * g - c * f) / det, (b * f - a * g) / det))
+            }
+            (true, false) if c > 0.0 => Some((0.0, -g / c)),
+            (false, true) if a > 0.0 => Some((-f / a, 0.0)),
+            _ => None,
+        };
+        let (suu, suv, svv) = (su.dot(&su), su.dot(&sv), sv.dot(&sv));
+        // J = [[fu, fv], [gu, gv]], fv = gu, gauss-newton drops the terms of r
+        let newton = solve(
+            suu + r.dot(&s[2][0]),
+            suv + r.dot(&s[1][1]),
+            svv + r.dot(&s[0][2]),
+        );
+        let gauss_newton = solve(suu, suv, svv);
+        let gradient = solve(suu, 0.0, svv);
+        let mut next = None;
+        'search: for (du, dv) in [newton, gauss_newton, gradient].into_iter().flatten() {
+            let mut scale = 1.0;
+            while (su * (du * scale) + sv * (dv * scale)).norm() >= INVERSION_POINT_TOLERANCE {
+                let next_u = (u + du * scale).clamp(u_start, u_end);
+                let next_v = (v + dv * scale).clamp(v_start, v_end);
+                let next_point = point(next_u, next_v);
+                let next_distance = next_point.distance(target);
+                if next_distance <= distance {
+                    next = Some((next_u, next_v, next_point, next_distance));
+                    break 'search;
+                }
+                scale *= 0.5;
+            }
+        }
+        // every step increases the distance, (u, v) is a local minimum up to rounding
+        let Some((next_u, next_v, next_point, next_distance)) = next else {
+            return Ok((u, v, s[0][0], distance));
+        };
+        let step = (su * (next_u - u) + sv * (next_v - v)).norm();
+        (u, v) = (next_u, next_v);
+        // the distance does not decrease any more up to rounding
+        if step < INVERSION_POINT_TOLERANCE || next_distance == distance {
+            return Ok((u, v, next_point, next_distance));
+        }
+    }
+    Err(BasisFunctionError::NotConverged)
+}
+
+/// elliptic arc C(θ) = center + x_radius cos(θ) x_axis + y_radius sin(θ) y_axis
+/// for θ from start to end (NURBS Book A7.1), one rational quadratic segment
+/// for at most every 90 degrees
+///
+/// output: knots on \[0, 1] and homogeneous control points
+pub(crate) fn elliptic_arc<P>(
+    center: P,
+    x_axis: P,
+    y_axis: P,
+    x_radius: f64,
+    y_radius: f64,
+    start: f64,
+    end: f64,
+) -> (Knots, Vec<HomoControlPoint<P>>)
+where
+    P: ControlPoint,
+{
+    let theta = end - start;
+    let narcs = ((theta / std::f64::consts::FRAC_PI_2).ceil() as usize).clamp(1, 4);
+    let dtheta = theta / narcs as f64;
+    let w1 = (dtheta / 2.0).cos();
+    let point = |angle: f64, scale: f64| {
+        center
+            + x_axis * (x_radius * angle.cos() * scale)
+            + y_axis * (y_radius * angle.sin() * scale)
+    };
+    let mut control_points = vec![HomoControlPoint::from_control_point(point(start, 1.0), 1.0)];
+    for i in 0..narcs {
+        let angle = start + dtheta * i as f64;
+        // tangents at both ends of segment meet at the middle angle, 1 / w1 away from center
+        control_points.push(HomoControlPoint::from_control_point(
+            point(angle + dtheta / 2.0, 1.0 / w1),
+            w1,
+        ));
+        let angle = if i + 1 == narcs { end } else { angle + dtheta };
+        control_points.push(HomoControlPoint::from_control_point(point(angle, 1.0), 1.0));
+    }
+    (double_interior_knots(narcs), control_points)
+}
+
+/// knots on \[0, 1] of n rational quadratic segments joined at double knots
+fn double_interior_knots(n: usize) -> Knots {
+    let mut knots = vec![0.0; 3];
+    for i in 1..n {
+        let u = i as f64 / n as f64;
+        knots.extend([u, u]);
+    }
+    knots.extend([1.0; 3]);
+    Knots::new(knots)
+}
+
+/// lines closer to parallel than this have no intersection
+pub(crate) const INTERSECTION_TOLERANCE: f64 = 1e-12;
+
+/// intersection of lines p0 + s t0 and p2 + t t2, the closest pair of points
+/// in least squares sense if lines are skew
+///
+/// output: (s, t), None if lines are parallel
+fn intersect_lines<P>(p0: P, t0: P, p2: P, t2: P) -> Option<(f64, f64)>
+where
+    P: ControlPoint + InnerProductSpace,
+{
+    let d = p2 - p0;
+    let (a, b, c) = (t0.dot(&t0), t0.dot(&t2), t2.dot(&t2));
+    let det = a * c - b * b;
+    if det.abs() <= INTERSECTION_TOLERANCE * a * c {
+        return None;
+    }
+    let (e, f) = (t0.dot(&d), t2.dot(&d));
+    Some(((e * c - b * f) / det, (b * e - a * f) / det))
+}
+
+/// one rational quadratic arc from p0 to p2 with end tangents t0, t2 passing through p
+/// (NURBS Book A7.2)
+///
+/// output: (w1 * P1, w1), P1 is at infinity and the first one is a vector if w1 = 0
+///
+/// error if p0, p, p2 and the tangents do not define a conic arc
+fn one_arc_conic<P>(p0: P, t0: P, p2: P, t2: P, p: P) -> Result<(P, f64), BasisFunctionError>
+where
+    P: ControlPoint + InnerProductSpace,
+{
+    let v02 = p2 - p0;
+    // the line through P1 and p meets chord p0 p2 at p0 + alf2 * v02
+    let ratio = |alf2: f64| {
+        if alf2 <= 0.0 || alf2 >= 1.0 {
+            return Err(BasisFunctionError::DegenerateConic);
+        }
+        let a = (alf2 / (1.0 - alf2)).sqrt();
+        Ok(a / (1.0 + a))
+    };
+    match intersect_lines(p0, t0, p2, t2) {
+        Some((alf0, _)) => {
+            let p1 = p0 + t0 * alf0;
+            let v1 = p - p1;
+            let (_, alf2) =
+                intersect_lines(p1, v1, p0, v02).ok_or(BasisFunctionError::DegenerateConic)?;
+            let u = ratio(alf2)?;
+            let num = (p - p0).dot(&v1) * (1.0 - u) * (1.0 - u) + (p - p2).dot(&v1) * u * u;
+            let den = 2.0 * u * (1.0 - u) * v1.dot(&v1);
+            if den == 0.0 {
+                return Err(BasisFunctionError::DegenerateConic);
+            }
+            let w1 = -num / den;
+            Ok((p1 * w1, w1))
+        }
+        None => {
+            // parallel tangents, p1 is the point at infinity along t0
+            let (alf0, alf2) =
+                intersect_lines(p, t0, p0, v02).ok_or(BasisFunctionError::DegenerateConic)?;
+            let u = ratio(alf2)?;
+            let b = 2.0 * u * (1.0 - u);
+            Ok((t0 * (-alf0 * (1.0 - b) / b), 0.0))
+        }
+    }
+}
+
+/// split rational quadratic arc p0, (w1 * P1, w1), p2 at its middle (NURBS Book Eq. 7.12)
+///
+/// output: (Q1, S, R1, weight of Q1 and R1), S is the middle point of arc
+fn split_conic_arc<P>(p0: P, a1: P, w1: f64, p2: P) -> (P, P, P, f64)
+where
+    P: ControlPoint,
+{
+    let q1 = (p0 + a1) / (1.0 + w1);
+    let r1 = (a1 + p2) / (1.0 + w1);
+    (q1, (q1 + r1) / 2.0, r1, ((1.0 + w1) / 2.0).sqrt())
+}
+
+/// open conic arc from p0 to p2 with end tangents t0, t2 passing through p (NURBS Book A7.3),
+/// the arc is split into 1, 2 or 4 segments so that every weight is positive
+///
+/// output: knots on \[0, 1] and homogeneous control points
+///
+/// error if p0, p, p2 and the tangents do not define a conic arc
+pub(crate) fn conic_arc<P>(
+    p0: P,
+    t0: P,
+    p2: P,
+    t2: P,
+    p: P,
+) -> Result<(Knots, Vec<HomoControlPoint<P>>), BasisFunctionError>
+where
+    P: ControlPoint + InnerProductSpace,
+{
+    let (a1, w1) = one_arc_conic(p0, t0, p2, t2, p)?;
+    if w1 <= -1.0 {
+        return Err(BasisFunctionError::DegenerateConic);
+    }
+    // cosine of angle p0 P1 p2
+    let cosine = || {
+        let p1 = a1 / w1;
+        let (d0, d2) = (p0 - p1, p2 - p1);
+        d0.dot(&d2) / (d0.norm() * d2.norm())
+    };
+    let nsegs = if w1 >= 1.0 || (w1 > 0.0 && cosine() < 0.5) {
+        1
+    } else if w1 < 0.0 && cosine() < 0.0 {
+        4
+    } else {
+        2
+    };
+    let homo = HomoControlPoint::from_control_point;
+    let control_points = match nsegs {
+        1 => vec![homo(p0, 1.0), homo(a1 / w1, w1), homo(p2, 1.0)],
+        2 => {
+            let (q1, s, r1, wqr) = split_conic_arc(p0, a1, w1, p2);
+            vec![
+                homo(p0, 1.0),
+                homo(q1, wqr),
+                homo(s, 1.0),
+                homo(r1, wqr),
+                homo(p2, 1.0),
+            ]
+        }
+        _ => {
+            let (q1, s, r1, wqr) = split_conic_arc(p0, a1, w1, p2);
+            let (hq1, hs, hr1, w) = split_conic_arc(p0, q1 * wqr, wqr, s);
+            let (hq2, hs2, hr2, _) = split_conic_arc(s, r1 * wqr, wqr, p2);
+            vec![
+                homo(p0, 1.0),
+                homo(hq1, w),
+                homo(hs, 1.0),
+                homo(hr1, w),
+                homo(s, 1.0),
+                homo(hq2, w),
+                homo(hs2, 1.0),
+                homo(hr2, w),
+                homo(p2, 1.0),
+            ]
+        }
+    };
+    Ok((double_interior_knots(nsegs), control_points))
+}
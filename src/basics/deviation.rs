@@ -0,0 +1,35 @@
+/// deviation of fitted curve or surface from data points
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Deviation {
+    /// max distance
+    pub max: f64,
+    /// root mean square of distances
+    pub rms: f64,
+}
+
+impl Deviation {
+    pub(crate) fn from_distances(distances: impl Iterator<Item = f64>) -> Self {
+        let (max, sum, count) = distances.fold((0.0f64, 0.0, 0), |(max, sum, count), d| {
+            (max.max(d), sum + d * d, count + 1)
+        });
+        Self {
+            max,
+            rms: if count == 0 {
+                0.0
+            } else {
+                (sum / count as f64).sqrt()
+            },
+        }
+    }
+}
+
+#[test]
+fn test_deviation_from_distances() {
+    let deviation = Deviation::from_distances([3.0, 4.0, 0.0, 1.0].into_iter());
+    assert_eq!(deviation.max, 4.0);
+    assert_eq!(deviation.rms, (26.0f64 / 4.0).sqrt());
+    assert_eq!(
+        Deviation::from_distances(std::iter::empty()),
+        Deviation { max: 0.0, rms: 0.0 }
+    );
+}
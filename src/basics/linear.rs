@@ -0,0 +1,132 @@
+// small linear solvers used by curve and surface fitting
+#![allow(clippy::needless_range_loop)]
+
+use crate::{basics::ControlPoint, basis_function::BasisFunctionError};
+
+/// pivots below this are treated as zero
+const SINGULAR_TOLERANCE: f64 = 1e-12;
+
+/// solve A x = b by gaussian elimination with partial pivoting,
+/// every right hand side is a control point
+///
+/// error if A is singular
+pub(crate) fn solve<P>(mut a: Vec<Vec<f64>>, mut b: Vec<P>) -> Result<Vec<P>, BasisFunctionError>
+where
+    P: ControlPoint,
+{
+    let n = b.len();
+    for k in 0..n {
+        let pivot = (k..n)
+            .max_by(|&i, &j| a[i][k].abs().total_cmp(&a[j][k].abs()))
+            .unwrap();
+        if a[pivot][k].abs() < SINGULAR_TOLERANCE {
+            return Err(BasisFunctionError::SingularMatrix);
+        }
+        a.swap(k, pivot);
+        b.swap(k, pivot);
+        for i in k + 1..n {
+            let factor = a[i][k] / a[k][k];
+            if factor == 0.0 {
+                continue;
+            }
+            for j in k..n {
+                a[i][j] -= factor * a[k][j];
+            }
+            let bk = b[k];
+            b[i] -= bk * factor;
+        }
+    }
+    for k in (0..n).rev() {
+        let mut sum = b[k];
+        for j in k + 1..n {
+            sum -= b[j] * a[k][j];
+        }
+        b[k] = sum / a[k][k];
+    }
+    Ok(b)
+}
+
+/// solve A x = b for banded A by gaussian elimination without pivoting,
+/// A is meant to be symmetric positive definite like normal equations of least squares
+///
+/// band\[i]\[j + w - i] = A_ij for |i - j| <= w, w is half bandwidth
+///
+/// error if a zero pivot is met
+pub(crate) fn solve_banded<P>(
+    mut band: Vec<Vec<f64>>,
+    w: usize,
+    mut b: Vec<P>,
+) -> Result<Vec<P>, BasisFunctionError>
+where
+    P: ControlPoint,
+{
+    let n = b.len();
+    for k in 0..n {
+        let pivot = band[k][w];
+        if pivot.abs() < SINGULAR_TOLERANCE {
+            return Err(BasisFunctionError::SingularMatrix);
+        }
+        for i in k + 1..n.min(k + w + 1) {
+            let factor = band[i][k + w - i] / pivot;
+            if factor == 0.0 {
+                continue;
+            }
+            for j in k..n.min(k + w + 1) {
+                band[i][j + w - i] -= factor * band[k][j + w - k];
+            }
+            let bk = b[k];
+            b[i] -= bk * factor;
+        }
+    }
+    for k in (0..n).rev() {
+        let mut sum = b[k];
+        for j in k + 1..n.min(k + w + 1) {
+            sum -= b[j] * band[k][j + w - k];
+        }
+        b[k] = sum / band[k][w];
+    }
+    Ok(b)
+}
+
+#[test]
+fn test_solve() {
+    let a = vec![
+        vec![0.0, 2.0, 1.0],
+        vec![1.0, 1.0, 0.0],
+        vec![3.0, 0.0, 1.0],
+    ];
+    // x = [1, 2, 3]
+    let x = solve(a, vec![7.0, 3.0, 6.0]).unwrap();
+    for (x, e) in x.iter().zip([1.0, 2.0, 3.0]) {
+        assert!((x - e).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_solve_singular() {
+    let a = vec![vec![1.0, 2.0], vec![2.0, 4.0]];
+    assert!(matches!(
+        solve(a, vec![1.0, 2.0]),
+        Err(BasisFunctionError::SingularMatrix)
+    ));
+}
+
+#[test]
+fn test_solve_banded() {
+    // tridiagonal [2 -1 0 0; -1 2 -1 0; 0 -1 2 -1; 0 0 -1 2], x = [1, 2, 3, 4]
+    let band = vec![
+        vec![0.0, 2.0, -1.0],
+        vec![-1.0, 2.0, -1.0],
+        vec![-1.0, 2.0, -1.0],
+        vec![-1.0, 2.0, 0.0],
+    ];
+    let x = solve_banded(band, 1, vec![0.0, 0.0, 0.0, 5.0]).unwrap();
+    for (x, e) in x.iter().zip([1.0, 2.0, 3.0, 4.0]) {
+        assert!((x - e).abs() < 1e-12);
+    }
+    let band = vec![vec![0.0, 1.0, 0.0], vec![0.0, 0.0, 0.0]];
+    assert!(matches!(
+        solve_banded(band, 1, vec![1.0, 1.0]),
+        Err(BasisFunctionError::SingularMatrix)
+    ));
+}
@@ -1,4 +1,11 @@
+mod deviation;
+mod parameterization;
 mod point;
 pub mod algorithm;
+pub(crate) mod linear;
+pub use deviation::Deviation;
+pub use parameterization::Parameterization;
 pub use point::ControlPoint;
-pub use point::HomoControlPoint;
\ No newline at end of file
+pub use point::HomoControlPoint;
+pub use point::InnerProductSpace;
+pub use point::Point;
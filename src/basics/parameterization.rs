@@ -0,0 +1,144 @@
+use super::InnerProductSpace;
+
+/// method to assign a parameter to every data point before fitting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parameterization {
+    /// equally spaced parameters
+    Uniform,
+    /// parameters proportional to distance between points
+    ChordLength,
+    /// parameters proportional to square root of distance between points
+    Centripetal,
+}
+
+impl Parameterization {
+    /// parameters of points in \[0, 1], first one is 0 and last one is 1
+    ///
+    /// uniform parameters are used if all points coincide
+    pub fn params<P>(&self, points: &[P]) -> Vec<f64>
+    where
+        P: InnerProductSpace,
+    {
+        self.try_params(points)
+            .unwrap_or_else(|| Parameterization::Uniform.try_params(points).unwrap())
+    }
+
+    /// parameters of a grid of points, points\[i]\[j] lies at (u_i, v_j),
+    /// u_i is averaged over every column and v_j is averaged over every row (NURBS Book A9.3),
+    /// degenerate rows or columns whose points all coincide are skipped
+    ///
+    /// output: (u params, v params)
+    pub fn grid_params<P>(&self, points: &[Vec<P>]) -> (Vec<f64>, Vec<f64>)
+    where
+        P: InnerProductSpace + Copy,
+    {
+        let columns = (0..points.first().map_or(0, |row| row.len()))
+            .map(|j| points.iter().map(|row| row[j]).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        (
+            self.average_params(&columns, points.len()),
+            self.average_params(points, columns.len()),
+        )
+    }
+
+    fn average_params<P>(&self, lines: &[Vec<P>], n: usize) -> Vec<f64>
+    where
+        P: InnerProductSpace,
+    {
+        let mut sum = vec![0.0; n];
+        let mut count = 0;
+        for params in lines.iter().filter_map(|line| self.try_params(line)) {
+            for (s, u) in sum.iter_mut().zip(params) {
+                *s += u;
+            }
+            count += 1;
+        }
+        if count == 0 {
+            return Parameterization::Uniform.try_params(&vec![0.0; n]).unwrap();
+        }
+        let mut params = sum
+            .into_iter()
+            .map(|s| s / count as f64)
+            .collect::<Vec<_>>();
+        if n >= 2 {
+            params[n - 1] = 1.0;
+        }
+        params
+    }
+
+    /// None if all points coincide
+    fn try_params<P>(&self, points: &[P]) -> Option<Vec<f64>>
+    where
+        P: InnerProductSpace,
+    {
+        let n = points.len();
+        if n < 2 {
+            return Some(vec![0.0; n]);
+        }
+        let steps = points
+            .windows(2)
+            .map(|w| match self {
+                Parameterization::Uniform => 1.0,
+                Parameterization::ChordLength => w[1].distance(&w[0]),
+                Parameterization::Centripetal => w[1].distance(&w[0]).sqrt(),
+            })
+            .collect::<Vec<_>>();
+        let total = steps.iter().sum::<f64>();
+        if total == 0.0 {
+            return None;
+        }
+        let mut params = Vec::with_capacity(n);
+        let mut acc = 0.0;
+        params.push(0.0);
+        for step in &steps[..n - 2] {
+            acc += step;
+            params.push(acc / total);
+        }
+        params.push(1.0);
+        Some(params)
+    }
+}
+
+#[test]
+fn test_parameterization_params() {
+    let points = [0.0, 1.0, 5.0, 9.0];
+    assert_eq!(
+        Parameterization::Uniform.params(&points),
+        vec![0.0, 1.0 / 3.0, 2.0 / 3.0, 1.0]
+    );
+    assert_eq!(
+        Parameterization::ChordLength.params(&points),
+        vec![0.0, 1.0 / 9.0, 5.0 / 9.0, 1.0]
+    );
+    assert_eq!(
+        Parameterization::Centripetal.params(&points),
+        vec![0.0, 0.2, 0.6, 1.0]
+    );
+    assert_eq!(
+        Parameterization::ChordLength.params(&[1.0, 1.0, 1.0]),
+        vec![0.0, 0.5, 1.0]
+    );
+}
+
+#[test]
+fn test_parameterization_grid_params() {
+    let points = vec![
+        vec![0.0, 1.0, 3.0],
+        vec![0.0, 0.0, 0.0],
+        vec![0.0, 3.0, 9.0],
+    ];
+    let (u, v) = Parameterization::ChordLength.grid_params(&points);
+    // first column is degenerate and skipped
+    assert_eq!(u, vec![0.0, 0.25, 1.0]);
+    // second row is degenerate and skipped
+    assert_eq!(v, vec![0.0, 1.0 / 3.0, 1.0]);
+}
+
+#[test]
+fn test_parameterization_params_of_arrays() {
+    let points = [[0.0, 0.0], [3.0, 4.0], [3.0, 4.0], [6.0, 8.0]];
+    assert_eq!(
+        Parameterization::ChordLength.params(&points),
+        vec![0.0, 0.5, 0.5, 1.0]
+    );
+}
@@ -1,6 +1,6 @@
 use std::{
     fmt::Debug,
-    ops::{Add, AddAssign, Div, Mul, Sub, SubAssign},
+    ops::{Add, AddAssign, Div, Index, IndexMut, Mul, Sub, SubAssign},
 };
 
 /// control point trait
@@ -37,6 +37,151 @@ where
     }
 }
 
+/// inner product space, provides length of and distance between points,
+/// used as the bound of geometric queries
+pub trait InnerProductSpace {
+    /// inner product
+    fn dot(&self, rhs: &Self) -> f64;
+
+    /// euclidean distance between two points
+    fn distance(&self, rhs: &Self) -> f64;
+
+    /// length of point as a vector
+    fn norm(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+}
+
+impl InnerProductSpace for f64 {
+    fn dot(&self, rhs: &Self) -> f64 {
+        self * rhs
+    }
+
+    fn distance(&self, rhs: &Self) -> f64 {
+        (self - rhs).abs()
+    }
+}
+
+impl<const N: usize> InnerProductSpace for [f64; N] {
+    fn dot(&self, rhs: &Self) -> f64 {
+        self.iter().zip(rhs).map(|(a, b)| a * b).sum()
+    }
+
+    fn distance(&self, rhs: &Self) -> f64 {
+        self.iter()
+            .zip(rhs)
+            .map(|(a, b)| (a - b) * (a - b))
+            .sum::<f64>()
+            .sqrt()
+    }
+}
+
+#[cfg(feature = "na")]
+impl<const D: usize> InnerProductSpace for nalgebra::SVector<f64, D> {
+    fn dot(&self, rhs: &Self) -> f64 {
+        nalgebra::Matrix::dot(self, rhs)
+    }
+
+    fn distance(&self, rhs: &Self) -> f64 {
+        (self - rhs).norm()
+    }
+}
+
+/// built-in point of D dimension, a control point without nalgebra
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point<const D: usize>(pub [f64; D]);
+
+impl<const D: usize> Point<D> {
+    pub fn new(coords: [f64; D]) -> Self {
+        Self(coords)
+    }
+
+    pub fn coords(&self) -> [f64; D] {
+        self.0
+    }
+}
+
+impl<const D: usize> Default for Point<D> {
+    fn default() -> Self {
+        Self([0.0; D])
+    }
+}
+
+impl<const D: usize> From<[f64; D]> for Point<D> {
+    fn from(coords: [f64; D]) -> Self {
+        Self(coords)
+    }
+}
+
+impl<const D: usize> Index<usize> for Point<D> {
+    type Output = f64;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<const D: usize> IndexMut<usize> for Point<D> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+impl<const D: usize> Add for Point<D> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+impl<const D: usize> Sub for Point<D> {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+impl<const D: usize> Mul<f64> for Point<D> {
+    type Output = Self;
+
+    fn mul(self, rhs: f64) -> Self::Output {
+        Self(self.0.map(|x| x * rhs))
+    }
+}
+impl<const D: usize> Div<f64> for Point<D> {
+    type Output = Self;
+
+    fn div(self, rhs: f64) -> Self::Output {
+        Self(self.0.map(|x| x / rhs))
+    }
+}
+impl<const D: usize> AddAssign for Point<D> {
+    fn add_assign(&mut self, rhs: Self) {
+        for (a, b) in self.0.iter_mut().zip(rhs.0) {
+            *a += b;
+        }
+    }
+}
+impl<const D: usize> SubAssign for Point<D> {
+    fn sub_assign(&mut self, rhs: Self) {
+        for (a, b) in self.0.iter_mut().zip(rhs.0) {
+            *a -= b;
+        }
+    }
+}
+
+impl<const D: usize> InnerProductSpace for Point<D> {
+    fn dot(&self, rhs: &Self) -> f64 {
+        self.0.dot(&rhs.0)
+    }
+
+    fn distance(&self, rhs: &Self) -> f64 {
+        self.0.distance(&rhs.0)
+    }
+}
+
 /// Control point trait was auto impl for HomoControlPoiny
 #[derive(Debug, Clone, Copy)]
 pub struct HomoControlPoint<CP: ControlPoint> {
@@ -119,7 +264,7 @@ impl<CP: ControlPoint> HomoControlPoint<CP> {
         (self.control_point, self.weight)
     }
 
-    pub fn to_control_point_and_weight(&self) -> (CP, f64) {
+    pub fn to_control_point_and_weight(self) -> (CP, f64) {
         let (fat_cp, w) = self.split();
         (fat_cp / w, w)
     }
@@ -131,3 +276,49 @@ impl<CP: ControlPoint> HomoControlPoint<CP> {
         }
     }
 }
+
+/// homogeneous control point is treated as a point in (D+1) dimension space
+impl<CP: ControlPoint + InnerProductSpace> InnerProductSpace for HomoControlPoint<CP> {
+    fn dot(&self, rhs: &Self) -> f64 {
+        self.control_point.dot(&rhs.control_point) + self.weight * rhs.weight
+    }
+
+    fn distance(&self, rhs: &Self) -> f64 {
+        let d = self.control_point.distance(&rhs.control_point);
+        let w = self.weight - rhs.weight;
+        (d * d + w * w).sqrt()
+    }
+}
+
+#[test]
+fn test_inner_product_space() {
+    let (a, b) = ([1.0, 2.0, 2.0], [4.0, 6.0, 2.0]);
+    assert_eq!(a.dot(&b), 20.0);
+    assert_eq!(a.norm(), 3.0);
+    assert_eq!(a.distance(&b), 5.0);
+    assert_eq!((-3.0f64).norm(), 3.0);
+    assert_eq!(1.0f64.distance(&-2.0), 3.0);
+    let (p, q) = (
+        HomoControlPoint::from_control_point(1.0, 2.0),
+        HomoControlPoint::from_control_point(-0.5, 4.0),
+    );
+    // (2, 2) and (-2, 4)
+    assert_eq!(p.dot(&q), 4.0);
+    assert_eq!(p.distance(&q), 20.0f64.sqrt());
+}
+
+#[test]
+fn test_point() {
+    let mut a = Point::new([1.0, 2.0, 2.0]);
+    let b = Point::from([4.0, 6.0, 2.0]);
+    assert_eq!(a + b, Point([5.0, 8.0, 4.0]));
+    assert_eq!(b - a, Point([3.0, 4.0, 0.0]));
+    assert_eq!(a * 2.0, Point([2.0, 4.0, 4.0]));
+    assert_eq!(b / 2.0, Point([2.0, 3.0, 1.0]));
+    assert_eq!(Point::<3>::zeros(), Point([0.0; 3]));
+    assert_eq!(a.norm(), 3.0);
+    assert_eq!(a.distance(&b), 5.0);
+    a += b;
+    a[2] -= 4.0;
+    assert_eq!(a.coords(), [5.0, 8.0, 0.0]);
+}
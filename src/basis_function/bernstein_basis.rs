@@ -1,6 +1,6 @@
-use super::{knots::Knots, Basis};
+use super::{check_domain, knots::Knots, Basis, BasisFunctionError};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BernsteinBasis {
     degree: usize,
     knots: Knots,
@@ -18,6 +18,36 @@ impl BernsteinBasis {
         let knots = Knots::new(v);
         Self { degree: n - 1, knots }
     }
+
+    /// create a bernstein basis function on domain \[a, b] by input the number of control points
+    ///
+    /// error if a >= b or a, b is not finite
+    pub fn new_on(n: usize, a: f64, b: f64) -> Result<Self, BasisFunctionError> {
+        check_domain(a, b)?;
+        Ok(Self::with_len_on(n, a, b))
+    }
+
+    /// basis of n functions on the same domain
+    pub(crate) fn with_len(&self, n: usize) -> Self {
+        let (a, b) = self.domain();
+        Self::with_len_on(n, a, b)
+    }
+
+    /// basis of n functions on \[a, b], a < b is not checked
+    pub(crate) fn with_len_on(n: usize, a: f64, b: f64) -> Self {
+        let n = if n == 0 { 1 } else { n };
+        let knots = Knots::new([vec![a; n], vec![b; n]].concat());
+        Self {
+            degree: n - 1,
+            knots,
+        }
+    }
+
+    /// map u in domain to \[0, 1]
+    pub(crate) fn to_unit(&self, u: f64) -> f64 {
+        let (a, b) = self.domain();
+        (u - a) / (b - a)
+    }
 }
 
 impl Basis for BernsteinBasis {
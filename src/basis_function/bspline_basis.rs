@@ -1,5 +1,6 @@
 use super::{Basis, BasisFunctionError, Knots};
 
+#[derive(Debug, Clone)]
 pub struct BsplineBasis {
     degree: usize,
     knots: Knots,
@@ -15,20 +16,7 @@ impl BsplineBasis {
     ///
     /// constraint: degree + 1 <= n
     pub fn new_uniform(degree: usize, n: usize) -> Result<Self, BasisFunctionError> {
-        if degree + 1 > n {
-            return Err(BasisFunctionError::TooLargeDegree);
-        }
-        // m = n + p + 1
-        // 0.0,...,0.0, ... ,1.0,...,1.0
-        //    p+1              p+1
-
-        let mut v = Vec::new();
-        let len = n - degree;
-        for i in 0..n - degree + 1 {
-            v.push(i as f64 / len as f64);
-        }
-
-        let knots = Knots::new([vec![0.0; degree], v, vec![1.0; degree]].concat());
+        let knots = Knots::open_uniform(degree, n)?;
         Ok(Self { degree, knots })
     }
 }
@@ -1,4 +1,4 @@
-///
+//
 // knot vector
 use std::ops::Index;
 
@@ -6,26 +6,196 @@ use super::BasisFunctionError;
 
 /// # trait of knot vector
 /// knot vector provides find_span() and Index
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Knots(pub(crate) Vec<f64>);
 
 impl Knots {
-    /// new a knot vector need user have knowledge on it
-    /// do not public to user
+    /// new a knot vector without validation, see `try_new` for the public one
     pub(crate) fn new(v: Vec<f64>) -> Self {
         Self(v)
     }
 
+    /// new a knot vector from non-decreasing finite values
+    ///
+    /// error if v has less than 2 knots, a knot is not finite or knots are decreasing somewhere
+    pub fn try_new(v: Vec<f64>) -> Result<Self, BasisFunctionError> {
+        if v.len() < 2 {
+            return Err(BasisFunctionError::KnotCountMismatch);
+        }
+        let knots = Self(v);
+        knots.check()?;
+        Ok(knots)
+    }
+
+    /// uniform unclamped knot vector of n control points,
+    /// n + degree + 1 equally spaced knots with domain \[0, 1]
+    ///
+    /// error if degree + 1 > n
+    pub fn uniform(degree: usize, n: usize) -> Result<Self, BasisFunctionError> {
+        if degree + 1 > n {
+            return Err(BasisFunctionError::TooLargeDegree);
+        }
+        let len = (n - degree) as f64;
+        Ok(Self(
+            (0..n + degree + 1)
+                .map(|i| (i as f64 - degree as f64) / len)
+                .collect(),
+        ))
+    }
+
+    /// open uniform (clamped) knot vector of n control points on \[0, 1],
+    /// end knots have multiplicity degree + 1 and interior knots are equally spaced
+    ///
+    /// error if degree + 1 > n
+    pub fn open_uniform(degree: usize, n: usize) -> Result<Self, BasisFunctionError> {
+        if degree + 1 > n {
+            return Err(BasisFunctionError::TooLargeDegree);
+        }
+        // m = n + p + 1
+        // 0.0,...,0.0, ... ,1.0,...,1.0
+        //    p+1              p+1
+        let len = n - degree;
+        let v = (0..=len).map(|i| i as f64 / len as f64).collect();
+        Ok(Self([vec![0.0; degree], v, vec![1.0; degree]].concat()))
+    }
+
+    /// knot vector of a closed curve with n distinct control points on domain \[0, 1],
+    /// the first degree control points are wrapped around to the end,
+    /// so the curve has n + degree control points and n + 2 * degree + 1 uniform knots
+    ///
+    /// error if degree + 1 > n
+    pub fn periodic(degree: usize, n: usize) -> Result<Self, BasisFunctionError> {
+        if degree + 1 > n {
+            return Err(BasisFunctionError::TooLargeDegree);
+        }
+        Self::uniform(degree, n + degree)
+    }
+
+    /// error if a knot is not finite or knots are decreasing somewhere
+    pub(crate) fn check(&self) -> Result<(), BasisFunctionError> {
+        if self.0.iter().any(|k| !k.is_finite()) {
+            return Err(BasisFunctionError::NonFiniteKnot);
+        }
+        if self.0.windows(2).any(|w| w[0] > w[1]) {
+            return Err(BasisFunctionError::NonMonotonicKnots);
+        }
+        Ok(())
+    }
+
     pub fn m(&self) -> usize {
         self.0.len() - 1
     }
 
-    /// find span of u in knot vector
+    /// number of times u appears in knot vector
+    pub fn multiplicity(&self, u: f64) -> usize {
+        self.0.iter().filter(|&&k| k == u).count()
+    }
+
+    pub fn as_slice(&self) -> &[f64] {
+        &self.0
+    }
+
+    /// distinct knots in order with their multiplicities
+    pub fn distinct(&self) -> Vec<(f64, usize)> {
+        let mut res: Vec<(f64, usize)> = Vec::new();
+        for &k in &self.0 {
+            match res.last_mut() {
+                Some((u, s)) if *u == k => *s += 1,
+                _ => res.push((k, 1)),
+            }
+        }
+        res
+    }
+
+    /// parameter domain \[u_p, u_m-p] of a curve of degree p on this knot vector
+    ///
+    /// panic if there are less than degree + 1 knots,
+    /// the domain is empty or inverted if there are less than 2 * degree + 2 knots
+    pub fn domain(&self, degree: usize) -> (f64, f64) {
+        (self[degree], self[self.m() - degree])
+    }
+
+    /// distinct knots inside the domain of degree, bounds of the nonempty knot spans
+    pub(crate) fn breakpoints(&self, degree: usize) -> Vec<f64> {
+        let (a, b) = self.domain(degree);
+        self.distinct()
+            .into_iter()
+            .map(|(u, _)| u)
+            .filter(|&u| a <= u && u <= b)
+            .collect()
+    }
+
+    /// true if the first and last degree + 1 knots are equal,
+    /// curve on clamped knots interpolates its end control points
+    pub fn is_clamped(&self, degree: usize) -> bool {
+        let v = &self.0;
+        v.len() >= 2 * (degree + 1)
+            && v[..=degree].iter().all(|&k| k == v[0])
+            && v[v.len() - degree - 1..]
+                .iter()
+                .all(|&k| k == v[v.len() - 1])
+    }
+
+    /// affinely map knots so that domain of degree becomes \[a, b]
+    ///
+    /// error if a >= b, a or b is not finite, degree is too large for knots or domain is empty
+    pub fn reparametrize(&self, degree: usize, a: f64, b: f64) -> Result<Self, BasisFunctionError> {
+        if self.0.len() < 2 * (degree + 1) {
+            return Err(BasisFunctionError::TooLargeDegree);
+        }
+        check_domain(a, b)?;
+        let (start, end) = self.domain(degree);
+        check_domain(start, end)?;
+        // knots on domain ends are mapped exactly
+        let v = self
+            .0
+            .iter()
+            .map(|&k| match k {
+                k if k == start => a,
+                k if k == end => b,
+                k => a + (k - start) / (end - start) * (b - a),
+            })
+            .collect();
+        Ok(Self(v))
+    }
+
+    /// reversed knot vector u'_i = a + b - u_m-i with the same domain \[a, b] of degree,
+    /// curve with reversed control points on it runs backwards
+    ///
+    /// panic if there are less than 2 * degree + 2 knots
+    pub fn reverse(&self, degree: usize) -> Self {
+        let (start, end) = self.domain(degree);
+        Self(
+            self.0
+                .iter()
+                .rev()
+                .map(|&k| match k {
+                    k if k == start => end,
+                    k if k == end => start,
+                    k => start + end - k,
+                })
+                .collect(),
+        )
+    }
+
+    /// reparametrize to domain \[0, 1]
+    pub fn normalize(&self, degree: usize) -> Result<Self, BasisFunctionError> {
+        self.reparametrize(degree, 0.0, 1.0)
+    }
+
+    /// find span of u in knot vector, u must lie in the domain \[u_p, u_m-p],
+    /// so that it works on clamped and unclamped knot vectors
+    ///
+    /// error if there are less than 2 * degree + 2 knots or u is not in the domain
     pub fn find_span(&self, degree: usize, u: f64) -> Result<usize, BasisFunctionError> {
-        if u < self[0] {
+        if self.0.len() < 2 * degree + 2 {
+            return Err(BasisFunctionError::TooLargeDegree);
+        }
+        let (start, end) = self.domain(degree);
+        if u < start {
             return Err(BasisFunctionError::ULessThanMin);
         }
-        if &u > self.0.last().unwrap() {
+        if u > end {
             return Err(BasisFunctionError::UGreaterThanMax);
         }
         if u.is_infinite() {
@@ -35,13 +205,17 @@ impl Knots {
             return Err(BasisFunctionError::UIsNon);
         }
         let n = self.m() - degree - 1;
-        // handle special case
+        // handle special case, u at the end of domain belongs to the last nonempty span
         // 0, 0, 0, 1/2, 1, 1, 1
         //              ^
         //              u
         //
-        if self.0.last().unwrap() == &u {
-            return Ok(n);
+        if u == end {
+            let mut span = n;
+            while span > degree && self[span] == end {
+                span -= 1;
+            }
+            return Ok(span);
         }
 
         let mut low = degree;
@@ -60,6 +234,17 @@ impl Knots {
     }
 }
 
+/// error if a or b is not finite or a >= b
+pub(crate) fn check_domain(a: f64, b: f64) -> Result<(), BasisFunctionError> {
+    if !a.is_finite() || !b.is_finite() {
+        return Err(BasisFunctionError::NonFiniteKnot);
+    }
+    if a >= b {
+        return Err(BasisFunctionError::EmptyDomain);
+    }
+    Ok(())
+}
+
 impl Index<usize> for Knots {
     type Output = f64;
 
@@ -84,6 +269,16 @@ fn test_knots_index() {
     }
 }
 
+#[test]
+fn test_knots_multiplicity() {
+    let v = vec![0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 4.0, 4.0, 5.0, 5.0, 5.0];
+    let knots = Knots::new(v);
+    assert_eq!(knots.multiplicity(0.0), 3);
+    assert_eq!(knots.multiplicity(1.0), 1);
+    assert_eq!(knots.multiplicity(4.0), 2);
+    assert_eq!(knots.multiplicity(0.5), 0);
+}
+
 #[test]
 fn test_knots_find_span_ok() {
     let v = vec![0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 4.0, 4.0, 5.0, 5.0, 5.0];
@@ -120,4 +315,109 @@ fn test_knots_find_span_err() {
         println!("{q}");
         assert!(result.is_err());
     }
+    let knots = Knots::try_new(vec![0.0, 1.0]).unwrap();
+    assert!(knots.find_span(0, 0.5).is_ok());
+    for degree in 1..=3 {
+        assert!(matches!(
+            knots.find_span(degree, 0.5),
+            Err(BasisFunctionError::TooLargeDegree)
+        ));
+    }
+}
+
+#[test]
+fn test_knots_check() {
+    assert!(Knots::new(vec![0.0, 0.0, 0.5, 1.0, 1.0]).check().is_ok());
+    assert!(matches!(
+        Knots::new(vec![0.0, 0.5, 0.4, 1.0]).check(),
+        Err(BasisFunctionError::NonMonotonicKnots)
+    ));
+    assert!(matches!(
+        Knots::new(vec![0.0, 0.5, f64::INFINITY]).check(),
+        Err(BasisFunctionError::NonFiniteKnot)
+    ));
+}
+
+#[test]
+fn test_knots_try_new() {
+    assert!(Knots::try_new(vec![0.0, 0.0, 1.0, 1.0]).is_ok());
+    assert!(matches!(
+        Knots::try_new(vec![0.0]),
+        Err(BasisFunctionError::KnotCountMismatch)
+    ));
+    assert!(matches!(
+        Knots::try_new(vec![0.0, 1.0, 0.5]),
+        Err(BasisFunctionError::NonMonotonicKnots)
+    ));
+    assert!(matches!(
+        Knots::try_new(vec![0.0, f64::NAN]),
+        Err(BasisFunctionError::NonFiniteKnot)
+    ));
+}
+
+#[test]
+fn test_knots_generators() {
+    assert_eq!(
+        Knots::uniform(2, 4).unwrap().as_slice(),
+        &[-1.0, -0.5, 0.0, 0.5, 1.0, 1.5, 2.0]
+    );
+    assert_eq!(
+        Knots::open_uniform(2, 4).unwrap().as_slice(),
+        &[0.0, 0.0, 0.0, 0.5, 1.0, 1.0, 1.0]
+    );
+    let periodic = Knots::periodic(2, 3).unwrap();
+    assert_eq!(periodic.m(), 3 + 2 * 2);
+    assert_eq!(periodic.domain(2), (0.0, 1.0));
+    assert!(Knots::uniform(3, 3).is_err());
+    assert!(Knots::open_uniform(3, 3).is_err());
+    assert!(Knots::periodic(3, 3).is_err());
+}
+
+#[test]
+fn test_knots_distinct_domain_clamped() {
+    let knots = Knots::new(vec![0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 4.0, 4.0, 5.0, 5.0, 5.0]);
+    assert_eq!(
+        knots.distinct(),
+        vec![(0.0, 3), (1.0, 1), (2.0, 1), (3.0, 1), (4.0, 2), (5.0, 3)]
+    );
+    assert_eq!(knots.domain(2), (0.0, 5.0));
+    assert!(knots.is_clamped(2));
+    assert!(!knots.is_clamped(3));
+    let knots = Knots::uniform(2, 4).unwrap();
+    assert_eq!(knots.domain(2), (0.0, 1.0));
+    assert!(!knots.is_clamped(2));
+}
+
+#[test]
+fn test_knots_reparametrize() {
+    let knots = Knots::new(vec![0.0, 0.0, 0.0, 0.25, 1.0, 1.0, 1.0]);
+    let moved = knots.reparametrize(2, 2.5, 17.0).unwrap();
+    assert_eq!(
+        moved.as_slice(),
+        &[2.5, 2.5, 2.5, 2.5 + 14.5 / 4.0, 17.0, 17.0, 17.0]
+    );
+    assert_eq!(moved.normalize(2).unwrap().as_slice(), knots.as_slice());
+    let knots = Knots::uniform(2, 4).unwrap();
+    let moved = knots.reparametrize(2, 0.0, 4.0).unwrap();
+    assert_eq!(moved.as_slice(), &[-4.0, -2.0, 0.0, 2.0, 4.0, 6.0, 8.0]);
+    assert!(knots.reparametrize(2, 1.0, 1.0).is_err());
+    assert!(knots.reparametrize(2, 0.0, f64::INFINITY).is_err());
+    assert!(knots.reparametrize(3, 0.0, 1.0).is_err());
+}
+
+#[test]
+fn test_knots_find_span_unclamped() {
+    let knots = Knots::uniform(2, 4).unwrap();
+    // -1, -0.5, 0, 0.5, 1, 1.5, 2 with domain [0, 1]
+    for (u, span) in [(0.0, 2), (0.25, 2), (0.5, 3), (0.75, 3), (1.0, 3)] {
+        assert_eq!(knots.find_span(2, u).unwrap(), span);
+    }
+    assert!(matches!(
+        knots.find_span(2, -0.25),
+        Err(BasisFunctionError::ULessThanMin)
+    ));
+    assert!(matches!(
+        knots.find_span(2, 1.25),
+        Err(BasisFunctionError::UGreaterThanMax)
+    ));
 }
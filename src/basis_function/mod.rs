@@ -4,17 +4,98 @@ mod knots;
 
 use std::collections::HashMap;
 
+pub(crate) use self::knots::check_domain;
 pub use self::knots::Knots;
 pub use bernstein_basis::BernsteinBasis;
 pub use bspline_basis::BsplineBasis;
 
+/// error of basis functions and of every algorithm on curves and surfaces,
+/// new variants may be added
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum BasisFunctionError {
     ULessThanMin,
     UGreaterThanMax,
     UIsInfinite,
     UIsNon,
     TooLargeDegree,
+    TooLargeMultiplicity,
+    NonMonotonicKnots,
+    NotInteriorKnot,
+    TooSmallDegree,
+    NotDegreeReducible(f64),
+    SingularMatrix,
+    TooFewPoints,
+    ToleranceNotReached(f64),
+    NotConverged,
+    NonFiniteKnot,
+    KnotCountMismatch,
+    RaggedControlNet,
+    NonPositiveWeight,
+    EmptyDomain,
+    NotClamped,
+    DegenerateConic,
+    LengthMismatch,
+    NonMonotonicParams,
+}
+
+impl std::fmt::Display for BasisFunctionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ULessThanMin => write!(f, "parameter is less than the start of domain"),
+            Self::UGreaterThanMax => write!(f, "parameter is greater than the end of domain"),
+            Self::UIsInfinite => write!(f, "parameter is infinite"),
+            Self::UIsNon => write!(f, "parameter is NaN"),
+            Self::TooLargeDegree => write!(f, "degree is too large for number of control points"),
+            Self::TooLargeMultiplicity => write!(f, "knot multiplicity would exceed degree"),
+            Self::NonMonotonicKnots => write!(f, "knots are decreasing"),
+            Self::NotInteriorKnot => write!(f, "knot is not an interior knot"),
+            Self::TooSmallDegree => write!(f, "degree is too small"),
+            Self::NotDegreeReducible(deviation) => {
+                write!(f, "curve is not degree reducible, deviation {deviation}")
+            }
+            Self::SingularMatrix => write!(f, "linear system is singular"),
+            Self::TooFewPoints => write!(f, "too few points"),
+            Self::ToleranceNotReached(deviation) => {
+                write!(f, "tolerance is not reached, deviation {deviation}")
+            }
+            Self::NotConverged => write!(f, "iteration does not converge"),
+            Self::NonFiniteKnot => write!(f, "knot is not finite"),
+            Self::KnotCountMismatch => write!(f, "number of knots does not match control points"),
+            Self::RaggedControlNet => write!(f, "rows of control net differ in length"),
+            Self::NonPositiveWeight => write!(f, "weight is not positive and finite"),
+            Self::EmptyDomain => write!(f, "domain or interval is empty"),
+            Self::NotClamped => write!(f, "knots are not clamped"),
+            Self::DegenerateConic => write!(f, "input does not define a conic"),
+            Self::LengthMismatch => write!(f, "inputs differ in length"),
+            Self::NonMonotonicParams => write!(f, "parameters are decreasing"),
+        }
+    }
+}
+
+impl std::error::Error for BasisFunctionError {}
+
+/// check that basis can carry n control points:
+/// knots are finite and non-decreasing, m + 1 = n + p + 1, n >= p + 1
+/// and domain \[u_p, u_m-p] is not empty
+pub(crate) fn check_basis<B: Basis>(basis: &B, n: usize) -> Result<(), BasisFunctionError> {
+    let p = basis.degree();
+    let knots = basis.knots();
+    knots.check()?;
+    if n == 0 {
+        return Err(BasisFunctionError::TooFewPoints);
+    }
+    if n < p + 1 {
+        return Err(BasisFunctionError::TooLargeDegree);
+    }
+    if knots.0.len() != n + p + 1 {
+        return Err(BasisFunctionError::KnotCountMismatch);
+    }
+    let (start, end) = knots.domain(p);
+    if start >= end {
+        return Err(BasisFunctionError::EmptyDomain);
+    }
+    Ok(())
 }
 
 pub trait Basis {
@@ -22,8 +103,19 @@ pub trait Basis {
 
     fn knots(&self) -> &Knots;
 
+    /// parameter domain \[u_p, u_m-p]
+    fn domain(&self) -> (f64, f64) {
+        self.knots().domain(self.degree())
+    }
+
+    /// panic if u is outside the domain, see `try_get_span`
     fn get_span(&self, u: f64) -> usize {
-        self.knots().find_span(self.degree(), u).unwrap()
+        self.try_get_span(u).unwrap()
+    }
+
+    /// span of u, u must lie in the domain \[u_p, u_m-p]
+    fn try_get_span(&self, u: f64) -> Result<usize, BasisFunctionError> {
+        self.knots().find_span(self.degree(), u)
     }
 
     /// input:
@@ -124,8 +216,8 @@ pub trait Basis {
         let a = a;
 
         // k=0
-        for j in 0..=p {
-            der[0][j] = *ndu.get(&[i - p + j, p]).unwrap();
+        for (j, d) in der[0].iter_mut().enumerate() {
+            *d = *ndu.get(&[i - p + j, p]).unwrap();
         }
 
         // 计算微分
@@ -133,7 +225,7 @@ pub trait Basis {
         for r in 0..=p {
             let _i = i - r;
             // N^{(k)}_{_i,p}
-            for k in 1..=p {
+            for (k, der_k) in der.iter_mut().enumerate().skip(1) {
                 let mut pre = 1.0;
                 for z in p - k + 1..=p {
                     pre *= z as f64;
@@ -145,7 +237,7 @@ pub trait Basis {
                         None => 0.0,
                     };
                 }
-                der[k][p - r] = pre * sum;
+                der_k[p - r] = pre * sum;
             }
         }
         der
@@ -154,7 +246,22 @@ pub trait Basis {
 
 #[cfg(test)]
 mod test {
-    use super::{Basis, BernsteinBasis, BsplineBasis};
+    use super::{Basis, BasisFunctionError, BernsteinBasis, BsplineBasis};
+
+    #[test]
+    fn test_basis_function_error_display() {
+        assert_eq!(
+            BasisFunctionError::EmptyDomain.to_string(),
+            "domain or interval is empty"
+        );
+        assert_eq!(
+            BasisFunctionError::ToleranceNotReached(0.5).to_string(),
+            "tolerance is not reached, deviation 0.5"
+        );
+        let error: Box<dyn std::error::Error> = Box::new(BasisFunctionError::UIsNon);
+        assert_eq!(error.to_string(), "parameter is NaN");
+    }
+
     // BernsteinBasis test
     #[test]
     fn test_bernstein_basis_get_value() {
@@ -0,0 +1,214 @@
+//! curve and surface types on built-in [`Point`], usable without nalgebra
+
+pub use crate::basics::Point;
+use crate::{
+    curve::{BezierCurveBase, BsplineCurveBase, NurbsCurveBase, RationalBezierCurveBase},
+    surface::{BezierSurfaceBase, BsplineSurfaceBase, NurbsSurfaceBase, RationalBezierSurfaceBase},
+};
+
+pub type BezierCurve<const D: usize> = BezierCurveBase<Point<D>>;
+pub type BsplineCurve<const D: usize> = BsplineCurveBase<Point<D>>;
+pub type NurbsCurve<const D: usize> = NurbsCurveBase<Point<D>>;
+pub type RationalBezierCurve<const D: usize> = RationalBezierCurveBase<Point<D>>;
+pub type BezierSurface<const D: usize> = BezierSurfaceBase<Point<D>>;
+pub type BsplineSurface<const D: usize> = BsplineSurfaceBase<Point<D>>;
+pub type NurbsSurface<const D: usize> = NurbsSurfaceBase<Point<D>>;
+pub type RationalBezierSurface<const D: usize> = RationalBezierSurfaceBase<Point<D>>;
+
+#[test]
+fn test_builtin_bspline_curve() {
+    use crate::basics::InnerProductSpace;
+    use crate::curve::NonRationalCurve;
+    let bspline = BsplineCurve::new(
+        vec![
+            Point([200.0, 200.0]),
+            Point([300.0, 300.0]),
+            Point([500.0, 400.0]),
+            Point([600.0, 300.0]),
+        ],
+        vec![0.0, 0.0, 0.0, 0.5, 1.0, 1.0, 1.0],
+        2,
+    );
+    assert_eq!(bspline.get_point(0.3), Point([320.0, 302.0]));
+    assert_eq!(bspline.get_point(0.5), Point([400.0, 350.0]));
+    assert_eq!(bspline.get_point(0.9), Point([560.0, 334.0]));
+    let (u, point, distance) = bspline.closest_point(&Point([400.0, 360.0]));
+    assert!((u - 0.5).abs() < 0.1);
+    assert!((point.distance(&Point([400.0, 360.0])) - distance).abs() < 1e-12);
+}
+
+#[test]
+fn test_builtin_nurbs_curve() {
+    use crate::basics::InnerProductSpace;
+    use crate::curve::RationalCurve;
+    let w = 0.5f64.sqrt();
+    let circle = NurbsCurve::new(
+        vec![
+            (Point([1.0, 0.0]), 1.0),
+            (Point([1.0, 1.0]), w),
+            (Point([0.0, 1.0]), 1.0),
+        ],
+        vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
+        2,
+    );
+    for i in 0..=10 {
+        let p = circle.get_point(i as f64 / 10.0);
+        assert!((p.norm() - 1.0).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_builtin_surfaces() {
+    use crate::surface::{NonRationalSurface, RationalSurface};
+    let control_points = (0..3)
+        .map(|i| {
+            (0..3)
+                .map(|j| Point([i as f64, j as f64, 0.0]))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let bezier = BezierSurface::new(control_points.clone());
+    assert_eq!(bezier.get_point(0.5, 0.25), Point([1.0, 0.5, 0.0]));
+    let bspline = BsplineSurface::new_uniform(control_points.clone(), 2, 2);
+    assert_eq!(
+        NonRationalSurface::get_point(&bspline, 0.5, 0.25),
+        Point([1.0, 0.5, 0.0])
+    );
+    let nurbs = NurbsSurface::new_uniform(
+        control_points
+            .iter()
+            .map(|row| row.iter().map(|&p| (p, 2.0)).collect())
+            .collect(),
+        2,
+        2,
+    );
+    assert_eq!(nurbs.get_point(0.5, 0.25), Point([1.0, 0.5, 0.0]));
+}
+
+#[test]
+fn test_builtin_try_new() {
+    use crate::basis_function::BasisFunctionError;
+    let points = vec![Point([0.0]), Point([1.0]), Point([2.0])];
+    assert!(BsplineCurve::try_new(points.clone(), vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0], 2).is_ok());
+    assert!(matches!(
+        BsplineCurve::try_new(points.clone(), vec![0.0, 0.0, 1.0, 1.0, 1.0], 2),
+        Err(BasisFunctionError::KnotCountMismatch)
+    ));
+    assert!(matches!(
+        BsplineCurve::try_new(points.clone(), vec![0.0, 0.0, 1.0, 0.5, 1.0, 1.0], 2),
+        Err(BasisFunctionError::NonMonotonicKnots)
+    ));
+    assert!(matches!(
+        BsplineCurve::try_new(points.clone(), vec![0.0, 0.0, 0.0, 1.0, 1.0, f64::NAN], 2),
+        Err(BasisFunctionError::NonFiniteKnot)
+    ));
+    assert!(matches!(
+        BsplineCurve::try_new(points[..2].to_vec(), vec![0.0; 4], 1),
+        Err(BasisFunctionError::EmptyDomain)
+    ));
+    assert!(matches!(
+        BsplineCurve::try_new_uniform(points.clone(), 3),
+        Err(BasisFunctionError::TooLargeDegree)
+    ));
+    assert!(matches!(
+        BsplineCurve::<1>::try_new_uniform(vec![], 0),
+        Err(BasisFunctionError::TooFewPoints)
+    ));
+    assert!(matches!(
+        BezierCurve::<1>::try_new(vec![]),
+        Err(BasisFunctionError::TooFewPoints)
+    ));
+    let weighted = points.iter().map(|&p| (p, 1.0)).collect::<Vec<_>>();
+    assert!(NurbsCurve::try_new(weighted.clone(), vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0], 2).is_ok());
+    let mut negative = weighted.clone();
+    negative[1].1 = -1.0;
+    assert!(matches!(
+        NurbsCurve::try_new(negative.clone(), vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0], 2),
+        Err(BasisFunctionError::NonPositiveWeight)
+    ));
+    assert!(matches!(
+        RationalBezierCurve::try_new(negative),
+        Err(BasisFunctionError::NonPositiveWeight)
+    ));
+
+    assert!(matches!(
+        BezierSurface::<1>::try_new(vec![]),
+        Err(BasisFunctionError::TooFewPoints)
+    ));
+    let ragged = vec![points.clone(), points[..2].to_vec()];
+    assert!(matches!(
+        BezierSurface::try_new(ragged.clone()),
+        Err(BasisFunctionError::RaggedControlNet)
+    ));
+    assert!(matches!(
+        BsplineSurface::try_new_uniform(ragged, 1, 1),
+        Err(BasisFunctionError::RaggedControlNet)
+    ));
+    let net = vec![points.clone(), points.clone()];
+    assert!(BsplineSurface::try_new_uniform(net.clone(), 1, 2).is_ok());
+    assert!(matches!(
+        BsplineSurface::try_new_uniform(net, 2, 2),
+        Err(BasisFunctionError::TooLargeDegree)
+    ));
+    let weighted_net = vec![weighted.clone(), weighted];
+    assert!(NurbsSurface::try_new_uniform(weighted_net.clone(), 1, 2).is_ok());
+    assert!(RationalBezierSurface::try_new(weighted_net.clone()).is_ok());
+    let mut zero = weighted_net;
+    zero[1][2].1 = 0.0;
+    assert!(matches!(
+        NurbsSurface::try_new_uniform(zero.clone(), 1, 2),
+        Err(BasisFunctionError::NonPositiveWeight)
+    ));
+    assert!(matches!(
+        RationalBezierSurface::try_new(zero),
+        Err(BasisFunctionError::NonPositiveWeight)
+    ));
+}
+
+#[test]
+fn test_builtin_try_get_point() {
+    use crate::basis_function::BasisFunctionError;
+    use crate::curve::{NonRationalCurve, RationalCurve};
+    use crate::surface::NonRationalSurface;
+    let points = vec![Point([0.0]), Point([1.0]), Point([2.0])];
+    let bspline = BsplineCurve::new_uniform(points.clone(), 2);
+    assert_eq!(bspline.try_get_point(0.5).unwrap(), Point([1.0]));
+    assert_eq!(bspline.try_get_ders(1, 0.5).unwrap()[1], Point([2.0]));
+    assert!(matches!(
+        bspline.try_get_point(1.5),
+        Err(BasisFunctionError::UGreaterThanMax)
+    ));
+    assert!(matches!(
+        bspline.try_get_ders(1, -0.5),
+        Err(BasisFunctionError::ULessThanMin)
+    ));
+    assert!(matches!(
+        bspline.try_get_point(f64::NAN),
+        Err(BasisFunctionError::UIsNon)
+    ));
+    // unchecked constructor with too few knots
+    let broken = BsplineCurve::new(points.clone(), vec![0.0, 0.0, 1.0, 1.0], 2);
+    assert!(matches!(
+        broken.try_get_point(0.5),
+        Err(BasisFunctionError::KnotCountMismatch)
+    ));
+    let nurbs = NurbsCurve::new(
+        points.iter().map(|&p| (p, 2.0)).collect(),
+        vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
+        2,
+    );
+    assert_eq!(nurbs.try_get_point(0.5).unwrap(), Point([1.0]));
+    assert!(nurbs.try_get_ders(1, 2.0).is_err());
+
+    let surface = BsplineSurface::new_uniform(vec![points.clone(), points], 1, 2);
+    assert_eq!(surface.try_get_point(0.5, 0.5).unwrap(), Point([1.0]));
+    assert_eq!(
+        surface.try_get_ders(1, 0.5, 0.5).unwrap()[0][1],
+        Point([2.0])
+    );
+    assert!(matches!(
+        surface.try_get_point(0.5, 1.5),
+        Err(BasisFunctionError::UGreaterThanMax)
+    ));
+    assert!(surface.try_get_ders(1, -1.0, 0.5).is_err());
+}
@@ -1,10 +1,14 @@
 use crate::{
-    basics::{algorithm::de_casteljaul, ControlPoint},
-    basis_function::BernsteinBasis,
+    basics::{
+        algorithm::{bezier_degree_elevate, de_casteljaul, de_casteljaul_split},
+        ControlPoint,
+    },
+    basis_function::{BasisFunctionError, BernsteinBasis},
 };
 
 use super::{NonRationalCurve, ParametricCurve};
 
+#[derive(Debug, Clone)]
 pub struct BezierCurveBase<P>
 where
     P: ControlPoint,
@@ -15,7 +19,7 @@ where
 
 impl<P> BezierCurveBase<P>
 where
-    P: ControlPoint, 
+    P: ControlPoint,
 {
     pub fn new(control_points: Vec<P>) -> Self {
         Self {
@@ -23,6 +27,102 @@ where
             control_points,
         }
     }
+
+    /// error if control_points is empty
+    pub fn try_new(control_points: Vec<P>) -> Result<Self, BasisFunctionError> {
+        if control_points.is_empty() {
+            return Err(BasisFunctionError::TooFewPoints);
+        }
+        Ok(Self::new(control_points))
+    }
+
+    /// curve on domain \[a, b] instead of \[0, 1]
+    ///
+    /// error if control_points is empty, a >= b or a, b is not finite
+    pub fn new_on(control_points: Vec<P>, a: f64, b: f64) -> Result<Self, BasisFunctionError> {
+        let curve = Self::try_new(control_points)?;
+        Ok(Self {
+            basis_function: BernsteinBasis::new_on(curve.control_points.len(), a, b)?,
+            ..curve
+        })
+    }
+
+    pub(crate) fn from_basis(basis_function: BernsteinBasis, control_points: Vec<P>) -> Self {
+        Self {
+            basis_function,
+            control_points,
+        }
+    }
+
+    /// curve on the same domain with other control points
+    fn with_control_points(&self, control_points: Vec<P>) -> Self {
+        Self {
+            basis_function: self.basis_function.with_len(control_points.len()),
+            control_points,
+        }
+    }
+
+    /// raise degree from p to p + t without changing the shape of curve
+    pub fn elevate_degree(&self, t: usize) -> Self {
+        self.with_control_points(bezier_degree_elevate(&self.control_points, t))
+    }
+
+    /// split curve at u into two curves on \[a, u] and \[u, b], \[a, b] is the domain of curve
+    ///
+    /// error if u is not inside the domain
+    pub fn split_at(&self, u: f64) -> Result<(Self, Self), BasisFunctionError> {
+        let (a, b) = self.domain();
+        if u <= a {
+            return Err(BasisFunctionError::ULessThanMin);
+        }
+        if u >= b {
+            return Err(BasisFunctionError::UGreaterThanMax);
+        }
+        let t = self.basis_function.to_unit(u);
+        let (left, right) = de_casteljaul_split(self.degree(), t, &self.control_points);
+        Ok((
+            Self::from_basis(BernsteinBasis::with_len_on(left.len(), a, u), left),
+            Self::from_basis(BernsteinBasis::with_len_on(right.len(), u, b), right),
+        ))
+    }
+
+    /// extract the part of curve on \[u0, u1]
+    ///
+    /// error if u1 <= u0 or \[u0, u1] is not inside the domain
+    pub fn sub_curve(&self, u0: f64, u1: f64) -> Result<Self, BasisFunctionError> {
+        let (a, b) = self.domain();
+        if u1 <= u0 {
+            return Err(BasisFunctionError::EmptyDomain);
+        }
+        if u0 < a {
+            return Err(BasisFunctionError::ULessThanMin);
+        }
+        if u1 > b {
+            return Err(BasisFunctionError::UGreaterThanMax);
+        }
+        let left = if u1 < b {
+            self.split_at(u1)?.0
+        } else {
+            self.clone()
+        };
+        if u0 > a {
+            Ok(left.split_at(u0)?.1)
+        } else {
+            Ok(left)
+        }
+    }
+
+    /// same curve on domain \[a, b]
+    ///
+    /// error if a >= b or a, b is not finite
+    pub fn reparametrize(&self, a: f64, b: f64) -> Result<Self, BasisFunctionError> {
+        Self::new_on(self.control_points.clone(), a, b)
+    }
+
+    /// same curve traversed backwards on the same domain
+    pub fn reverse(&self) -> Self {
+        self.with_control_points(self.control_points.iter().rev().copied().collect())
+    }
 }
 
 impl<P> ParametricCurve<P> for BezierCurveBase<P>
@@ -44,6 +144,10 @@ where
 {
     /// use deCasteljaul algorithm
     fn get_point(&self, u: f64) -> P {
-        de_casteljaul(self.degree(), u, &self.control_points)
+        de_casteljaul(
+            self.degree(),
+            self.basis_function.to_unit(u),
+            &self.control_points,
+        )
     }
 }
@@ -1,10 +1,18 @@
 use crate::{
-    basics::ControlPoint,
-    basis_function::{BsplineBasis, Knots},
+    basics::{
+        algorithm::{
+            curve_approximate, curve_clamp, curve_decompose, curve_degree_elevate,
+            curve_degree_reduce, curve_fit_least_squares, curve_interpolate, curve_knot_ins,
+            curve_knot_refine, curve_knot_remove, curve_split, curve_unclamp,
+        },
+        ControlPoint, InnerProductSpace, Parameterization,
+    },
+    basis_function::{check_basis, Basis, BasisFunctionError, BernsteinBasis, BsplineBasis, Knots},
 };
 
-use super::{NonRationalCurve, ParametricCurve};
+use super::{BezierCurveBase, NonRationalCurve, ParametricCurve};
 
+#[derive(Debug, Clone)]
 pub struct BsplineCurveBase<P>
 where
     P: ControlPoint,
@@ -24,13 +32,370 @@ where
         }
     }
 
-    /// panic if length of control_points < degree + 1
+    /// error if knots are not finite and non-decreasing, degree + 1 > number of control points
+    /// or number of knots != number of control points + degree + 1
+    pub fn try_new(
+        control_points: Vec<P>,
+        knots: Vec<f64>,
+        degree: usize,
+    ) -> Result<Self, BasisFunctionError> {
+        let basis_function = BsplineBasis::new(degree, Knots(knots));
+        check_basis(&basis_function, control_points.len())?;
+        Ok(Self::from_basis(basis_function, control_points))
+    }
+
+    /// panic if length of control_points < degree + 1, see `try_new_uniform`
     pub fn new_uniform(control_points: Vec<P>, degree: usize) -> Self {
+        Self::try_new_uniform(control_points, degree).unwrap()
+    }
+
+    /// error if control_points is empty or its length < degree + 1
+    pub fn try_new_uniform(
+        control_points: Vec<P>,
+        degree: usize,
+    ) -> Result<Self, BasisFunctionError> {
+        if control_points.is_empty() {
+            return Err(BasisFunctionError::TooFewPoints);
+        }
+        Ok(Self {
+            basis_function: BsplineBasis::new_uniform(degree, control_points.len())?,
+            control_points,
+        })
+    }
+
+    /// closed curve with C^(degree-1) continuity everywhere on domain \[0, 1],
+    /// the first degree control points are wrapped around to the end,
+    /// knots are uniform and unclamped, see `Knots::periodic`
+    ///
+    /// error if control_points is empty or its length < degree + 1
+    pub fn new_periodic(control_points: Vec<P>, degree: usize) -> Result<Self, BasisFunctionError> {
+        if control_points.is_empty() {
+            return Err(BasisFunctionError::TooFewPoints);
+        }
+        let knots = Knots::periodic(degree, control_points.len())?;
+        let wrapped = [&control_points[..], &control_points[..degree]].concat();
+        Ok(Self::from_basis(BsplineBasis::new(degree, knots), wrapped))
+    }
+
+    pub(crate) fn from_basis(basis_function: BsplineBasis, control_points: Vec<P>) -> Self {
+        Self {
+            basis_function,
+            control_points,
+        }
+    }
+
+    /// least squares approximation of points with fixed end points (NURBS Book A9.7),
+    /// the curve passes through the first and last point and approximates the others
+    ///
+    /// params: non-decreasing parameters of points, e.g. from `Parameterization::params`
+    ///
+    /// error if degree is 0, n_control_points is less than degree + 1 or more than points,
+    /// params and points differ in length, params are not finite, decrease or are all equal,
+    /// or the normal equations are singular
+    pub fn fit_least_squares(
+        points: &[P],
+        degree: usize,
+        n_control_points: usize,
+        params: &[f64],
+    ) -> Result<Self, BasisFunctionError> {
+        let (knots, control_points) =
+            curve_fit_least_squares(degree, points, params, None, n_control_points)?;
+        Ok(Self {
+            basis_function: BsplineBasis::new(degree, knots),
+            control_points,
+        })
+    }
+
+    /// weighted least squares approximation of points with fixed end points,
+    /// points with larger weights are approximated more closely
+    ///
+    /// error same as `fit_least_squares`, or if weights and points differ in length
+    pub fn fit_weighted_least_squares(
+        points: &[P],
+        weights: &[f64],
+        degree: usize,
+        n_control_points: usize,
+        params: &[f64],
+    ) -> Result<Self, BasisFunctionError> {
+        let (knots, control_points) =
+            curve_fit_least_squares(degree, points, params, Some(weights), n_control_points)?;
+        Ok(Self {
+            basis_function: BsplineBasis::new(degree, knots),
+            control_points,
+        })
+    }
+
+    /// insert knot u `times` times without changing the shape of curve
+    ///
+    /// error if multiplicity of u would exceed degree
+    pub fn insert_knot(&self, u: f64, times: usize) -> Result<Self, BasisFunctionError> {
+        let degree = self.degree();
+        let (knots, control_points) = curve_knot_ins(
+            degree,
+            self.basis_function.knots(),
+            &self.control_points,
+            u,
+            times,
+        )?;
+        Ok(Self {
+            basis_function: BsplineBasis::new(degree, knots),
+            control_points,
+        })
+    }
+
+    /// insert all knots in x at once without changing the shape of curve,
+    /// x should be sorted in non-decreasing order
+    pub fn refine_knots(&self, x: &[f64]) -> Result<Self, BasisFunctionError> {
+        let degree = self.degree();
+        let (knots, control_points) =
+            curve_knot_refine(degree, self.basis_function.knots(), &self.control_points, x)?;
+        Ok(Self {
+            basis_function: BsplineBasis::new(degree, knots),
+            control_points,
+        })
+    }
+
+    /// raise degree from p to p + t without changing the shape of curve,
+    /// an unclamped curve is clamped first
+    pub fn elevate_degree(&self, t: usize) -> Self {
+        let degree = self.degree();
+        if !self.basis_function.knots().is_clamped(degree) {
+            return self.clamp().elevate_degree(t);
+        }
+        let (knots, control_points) =
+            curve_degree_elevate(degree, self.basis_function.knots(), &self.control_points, t);
         Self {
-            basis_function: BsplineBasis::new_uniform(degree, control_points.len()).unwrap(),
+            basis_function: BsplineBasis::new(degree + t, knots),
             control_points,
         }
     }
+
+    /// decompose curve into bezier segments,
+    /// the i-th segment is on the i-th nonempty knot span \[u_i, u_i+1] of the domain,
+    /// an unclamped curve is clamped first
+    pub fn decompose(&self) -> Vec<BezierCurveBase<P>> {
+        if !self.basis_function.knots().is_clamped(self.degree()) {
+            return self.clamp().decompose();
+        }
+        let breakpoints = self.basis_function.knots().breakpoints(self.degree());
+        curve_decompose(
+            self.degree(),
+            self.basis_function.knots(),
+            &self.control_points,
+        )
+        .into_iter()
+        .zip(breakpoints.windows(2))
+        .map(|(segment, span)| {
+            BezierCurveBase::from_basis(
+                BernsteinBasis::with_len_on(segment.len(), span[0], span[1]),
+                segment,
+            )
+        })
+        .collect()
+    }
+
+    /// split curve at u into two curves on \[u_p, u] and \[u, u_m-p]
+    ///
+    /// error if u is not inside the domain of curve
+    pub fn split_at(&self, u: f64) -> Result<(Self, Self), BasisFunctionError> {
+        let degree = self.degree();
+        let ((left_knots, left), (right_knots, right)) =
+            curve_split(degree, self.basis_function.knots(), &self.control_points, u)?;
+        Ok((
+            Self {
+                basis_function: BsplineBasis::new(degree, left_knots),
+                control_points: left,
+            },
+            Self {
+                basis_function: BsplineBasis::new(degree, right_knots),
+                control_points: right,
+            },
+        ))
+    }
+
+    /// extract the part of curve on \[u0, u1]
+    ///
+    /// error if u1 <= u0 or \[u0, u1] is not inside the domain of curve
+    pub fn sub_curve(&self, u0: f64, u1: f64) -> Result<Self, BasisFunctionError> {
+        let (start, end) = self.domain();
+        if u1 <= u0 {
+            return Err(BasisFunctionError::EmptyDomain);
+        }
+        if u0 < start {
+            return Err(BasisFunctionError::ULessThanMin);
+        }
+        if u1 > end {
+            return Err(BasisFunctionError::UGreaterThanMax);
+        }
+        let right = if u0 > start {
+            self.split_at(u0)?.1
+        } else {
+            self.clone()
+        };
+        if u1 < end {
+            Ok(right.split_at(u1)?.0)
+        } else {
+            Ok(right)
+        }
+    }
+
+    /// same curve on domain \[a, b], knots are mapped affinely
+    ///
+    /// error if a >= b or a, b is not finite
+    pub fn reparametrize(&self, a: f64, b: f64) -> Result<Self, BasisFunctionError> {
+        let degree = self.degree();
+        let knots = self.basis_function.knots().reparametrize(degree, a, b)?;
+        Ok(Self::from_basis(
+            BsplineBasis::new(degree, knots),
+            self.control_points.clone(),
+        ))
+    }
+
+    /// same curve traversed backwards on the same domain
+    pub fn reverse(&self) -> Self {
+        let degree = self.degree();
+        Self::from_basis(
+            BsplineBasis::new(degree, self.basis_function.knots().reverse(degree)),
+            self.control_points.iter().rev().copied().collect(),
+        )
+    }
+
+    /// same curve on clamped knots, it passes through its first and last control point,
+    /// domain is unchanged, e.g. to convert a periodic curve
+    pub fn clamp(&self) -> Self {
+        let (knots, control_points) = curve_clamp(
+            self.degree(),
+            self.basis_function.knots(),
+            &self.control_points,
+        );
+        Self::from_basis(BsplineBasis::new(self.degree(), knots), control_points)
+    }
+
+    /// same curve on unclamped knots (NURBS Book A12.1),
+    /// a closed curve with C^(p-1) continuity at the seam becomes periodic:
+    /// its first degree control points equal the last ones
+    ///
+    /// error if degree is 0 or curve is not clamped
+    pub fn unclamp(&self) -> Result<Self, BasisFunctionError> {
+        let degree = self.degree();
+        if degree == 0 {
+            return Err(BasisFunctionError::TooSmallDegree);
+        }
+        if !self.basis_function.knots().is_clamped(degree) {
+            return Err(BasisFunctionError::NotClamped);
+        }
+        let (knots, control_points) =
+            curve_unclamp(degree, self.basis_function.knots(), &self.control_points);
+        Ok(Self::from_basis(
+            BsplineBasis::new(degree, knots),
+            control_points,
+        ))
+    }
+}
+
+impl<P> BsplineCurveBase<P>
+where
+    P: ControlPoint + InnerProductSpace,
+{
+    /// global interpolation of points (NURBS Book A9.1), the curve passes through every
+    /// point, knots are placed by averaging parameters of points
+    ///
+    /// end_derivatives: first derivatives at start and end of curve if given,
+    /// they add two control points, so degree can be up to number of points + 1
+    ///
+    /// error if degree is 0, or 1 with end_derivatives, points are too few for degree
+    /// or the linear system is singular
+    pub fn interpolate(
+        points: &[P],
+        degree: usize,
+        parameterization: Parameterization,
+        end_derivatives: Option<(P, P)>,
+    ) -> Result<Self, BasisFunctionError> {
+        let params = parameterization.params(points);
+        let (knots, control_points) = curve_interpolate(degree, points, &params, end_derivatives)?;
+        Ok(Self {
+            basis_function: BsplineBasis::new(degree, knots),
+            control_points,
+        })
+    }
+
+    /// error-bounded approximation of points, starts with degree + 1 control points and
+    /// adds knots where points deviate more than eps, until every point lies within eps
+    /// of the curve at its param, the curve passes through the first and last point
+    ///
+    /// params: non-decreasing parameters of points, e.g. from `Parameterization::params`
+    ///
+    /// output: (curve, max deviation of points)
+    ///
+    /// error if degree is 0, points are less than degree + 1, params and points differ
+    /// in length, params are not finite, decrease or are all equal,
+    /// or with max deviation if eps can not be reached
+    pub fn approximate(
+        points: &[P],
+        degree: usize,
+        params: &[f64],
+        eps: f64,
+    ) -> Result<(Self, f64), BasisFunctionError> {
+        let (knots, control_points, deviation) = curve_approximate(degree, points, params, eps)?;
+        Ok((
+            Self {
+                basis_function: BsplineBasis::new(degree, knots),
+                control_points,
+            },
+            deviation,
+        ))
+    }
+
+    /// try to remove knot u `times` times, a removal is accepted only if
+    /// the curve deviates from original one no more than tolerance
+    ///
+    /// output: (new curve, number of times u was actually removed, max deviation)
+    ///
+    /// error if u is not an interior knot
+    pub fn remove_knot(
+        &self,
+        u: f64,
+        times: usize,
+        tolerance: f64,
+    ) -> Result<(Self, usize, f64), BasisFunctionError> {
+        let degree = self.degree();
+        let (knots, control_points, removed, deviation) = curve_knot_remove(
+            degree,
+            self.basis_function.knots(),
+            &self.control_points,
+            u,
+            times,
+            tolerance,
+        )?;
+        Ok((
+            Self {
+                basis_function: BsplineBasis::new(degree, knots),
+                control_points,
+            },
+            removed,
+            deviation,
+        ))
+    }
+
+    /// reduce degree from p to p - 1 if the reduced curve deviates from original one
+    /// no more than tolerance, an unclamped curve is clamped first
+    ///
+    /// error with the max deviation bound if curve is not degree reducible
+    pub fn reduce_degree(&self, tolerance: f64) -> Result<Self, BasisFunctionError> {
+        if !self.basis_function.knots().is_clamped(self.degree()) {
+            return self.clamp().reduce_degree(tolerance);
+        }
+        let degree = self.degree();
+        let (knots, control_points, deviation) =
+            curve_degree_reduce(degree, self.basis_function.knots(), &self.control_points)?;
+        if deviation > tolerance {
+            return Err(BasisFunctionError::NotDegreeReducible(deviation));
+        }
+        Ok(Self {
+            basis_function: BsplineBasis::new(degree - 1, knots),
+            control_points,
+        })
+    }
 }
 
 impl<P> ParametricCurve<P> for BsplineCurveBase<P>
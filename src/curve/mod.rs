@@ -1,16 +1,18 @@
-use crate::basics::{ControlPoint, HomoControlPoint};
-use crate::basis_function::Basis;
+use crate::basics::{ControlPoint, HomoControlPoint, InnerProductSpace};
+use crate::basis_function::{check_basis, Basis, BasisFunctionError};
 
 use crate::basics::algorithm::get_rational_ders;
-use crate::basics::algorithm::{get_curve_point, get_curve_ders};
+use crate::basics::algorithm::{curve_closest_point, get_curve_ders, get_curve_point};
 
 mod bezier;
 mod bspline;
 mod nurbs;
+mod rational_bezier;
 
 pub use bezier::BezierCurveBase;
 pub use bspline::BsplineCurveBase;
 pub use nurbs::NurbsCurveBase;
+pub use rational_bezier::RationalBezierCurveBase;
 
 pub trait ParametricCurve<P> {
     type BasisFunction: Basis;
@@ -19,6 +21,28 @@ pub trait ParametricCurve<P> {
     fn degree(&self) -> usize {
         self.basis_function().degree()
     }
+
+    /// parameter domain (start, end) of curve
+    fn domain(&self) -> (f64, f64) {
+        self.basis_function().domain()
+    }
+
+    /// error if knots or control points are invalid, or u is outside the domain
+    fn check_parameter(&self, u: f64) -> Result<(), BasisFunctionError> {
+        check_basis(self.basis_function(), self.control_points().len())?;
+        self.basis_function().try_get_span(u).map(|_| ())
+    }
+}
+
+/// error if a weight is not positive and finite
+pub(crate) fn check_weights(
+    weights: impl IntoIterator<Item = f64>,
+) -> Result<(), BasisFunctionError> {
+    if weights.into_iter().all(|w| w > 0.0 && w.is_finite()) {
+        Ok(())
+    } else {
+        Err(BasisFunctionError::NonPositiveWeight)
+    }
 }
 
 /// 非有理曲线
@@ -41,6 +65,38 @@ where
             u,
         )
     }
+
+    /// same as `get_point` but never panics
+    ///
+    /// error if knots or control points are invalid, or u is outside the domain
+    fn try_get_point(&self, u: f64) -> Result<P, BasisFunctionError> {
+        self.check_parameter(u)?;
+        Ok(self.get_point(u))
+    }
+
+    /// same as `get_ders` but never panics
+    ///
+    /// error if knots or control points are invalid, or u is outside the domain
+    fn try_get_ders(&self, der_upper_bond: usize, u: f64) -> Result<Vec<P>, BasisFunctionError> {
+        self.check_parameter(u)?;
+        Ok(self.get_ders(der_upper_bond, u))
+    }
+
+    /// closest point on curve to p (NURBS Book 6.1),
+    /// the nearest of sampled points is refined by newton iteration
+    ///
+    /// output: (u, point, distance)
+    fn closest_point(&self, p: &P) -> (f64, P, f64)
+    where
+        P: InnerProductSpace,
+    {
+        curve_closest_point(
+            self.basis_function(),
+            p,
+            |u| self.get_point(u),
+            |u| self.get_ders(self.degree().min(2), u),
+        )
+    }
 }
 
 /// 有理曲线
@@ -61,4 +117,36 @@ where
             u,
         )
     }
+
+    /// same as `get_point` but never panics
+    ///
+    /// error if knots or control points are invalid, or u is outside the domain
+    fn try_get_point(&self, u: f64) -> Result<P, BasisFunctionError> {
+        self.check_parameter(u)?;
+        Ok(self.get_point(u))
+    }
+
+    /// same as `get_ders` but never panics
+    ///
+    /// error if knots or control points are invalid, or u is outside the domain
+    fn try_get_ders(&self, der_upper_bond: usize, u: f64) -> Result<Vec<P>, BasisFunctionError> {
+        self.check_parameter(u)?;
+        Ok(self.get_ders(der_upper_bond, u))
+    }
+
+    /// closest point on curve to p (NURBS Book 6.1),
+    /// the nearest of sampled points is refined by newton iteration
+    ///
+    /// output: (u, point, distance)
+    fn closest_point(&self, p: &P) -> (f64, P, f64)
+    where
+        P: InnerProductSpace,
+    {
+        curve_closest_point(
+            self.basis_function(),
+            p,
+            |u| self.get_point(u),
+            |u| self.get_ders(self.degree().min(2), u),
+        )
+    }
 }
@@ -1,10 +1,19 @@
 use crate::{
-    basics::{ControlPoint, HomoControlPoint},
-    basis_function::{BsplineBasis, Knots},
+    basics::{
+        algorithm::{
+            conic_arc, curve_clamp, curve_decompose, curve_degree_elevate, curve_degree_reduce,
+            curve_knot_ins, curve_knot_refine, curve_knot_remove, curve_split, curve_unclamp,
+            elliptic_arc, INTERSECTION_TOLERANCE,
+        },
+        ControlPoint, HomoControlPoint, InnerProductSpace,
+    },
+    basis_function::{check_basis, Basis, BasisFunctionError, BernsteinBasis, BsplineBasis, Knots},
 };
+use std::f64::consts::TAU;
 
-use super::{ParametricCurve, RationalCurve};
+use super::{check_weights, ParametricCurve, RationalBezierCurveBase, RationalCurve};
 
+#[derive(Debug, Clone)]
 pub struct NurbsCurveBase<P>
 where
     P: ControlPoint,
@@ -27,6 +36,443 @@ where
             control_points,
         }
     }
+
+    /// error if a weight is not positive, knots are not finite and non-decreasing,
+    /// degree + 1 > number of control points or
+    /// number of knots != number of control points + degree + 1
+    pub fn try_new(
+        control_points_weights: Vec<(P, f64)>,
+        knots: Vec<f64>,
+        degree: usize,
+    ) -> Result<Self, BasisFunctionError> {
+        check_weights(control_points_weights.iter().map(|(_, w)| *w))?;
+        let curve = Self::new(control_points_weights, knots, degree);
+        check_basis(&curve.basis_function, curve.control_points.len())?;
+        Ok(curve)
+    }
+
+    /// closed curve with C^(degree-1) continuity everywhere on domain \[0, 1],
+    /// the first degree control points and weights are wrapped around to the end,
+    /// knots are uniform and unclamped, see `Knots::periodic`
+    ///
+    /// error if control_points_weights is empty, its length < degree + 1
+    /// or a weight is not positive
+    pub fn new_periodic(
+        control_points_weights: Vec<(P, f64)>,
+        degree: usize,
+    ) -> Result<Self, BasisFunctionError> {
+        if control_points_weights.is_empty() {
+            return Err(BasisFunctionError::TooFewPoints);
+        }
+        check_weights(control_points_weights.iter().map(|(_, w)| *w))?;
+        let knots = Knots::periodic(degree, control_points_weights.len())?;
+        let wrapped = control_points_weights
+            .iter()
+            .chain(&control_points_weights[..degree])
+            .map(|(p, w)| HomoControlPoint::<P>::from_control_point(*p, *w))
+            .collect();
+        Ok(Self::from_basis(BsplineBasis::new(degree, knots), wrapped))
+    }
+
+    pub(crate) fn from_basis(
+        basis_function: BsplineBasis,
+        control_points: Vec<HomoControlPoint<P>>,
+    ) -> Self {
+        Self {
+            basis_function,
+            control_points,
+        }
+    }
+
+    /// insert knot u `times` times without changing the shape of curve,
+    /// knot insertion is done on homogeneous control points
+    ///
+    /// error if multiplicity of u would exceed degree
+    pub fn insert_knot(&self, u: f64, times: usize) -> Result<Self, BasisFunctionError> {
+        let degree = self.degree();
+        let (knots, control_points) = curve_knot_ins(
+            degree,
+            self.basis_function.knots(),
+            &self.control_points,
+            u,
+            times,
+        )?;
+        Ok(Self {
+            basis_function: BsplineBasis::new(degree, knots),
+            control_points,
+        })
+    }
+
+    /// insert all knots in x at once without changing the shape of curve,
+    /// x should be sorted in non-decreasing order
+    ///
+    /// knots are inserted on homogeneous control points
+    pub fn refine_knots(&self, x: &[f64]) -> Result<Self, BasisFunctionError> {
+        let degree = self.degree();
+        let (knots, control_points) =
+            curve_knot_refine(degree, self.basis_function.knots(), &self.control_points, x)?;
+        Ok(Self {
+            basis_function: BsplineBasis::new(degree, knots),
+            control_points,
+        })
+    }
+
+    /// raise degree from p to p + t without changing the shape of curve,
+    /// an unclamped curve is clamped first
+    ///
+    /// degree elevation is done on homogeneous control points
+    pub fn elevate_degree(&self, t: usize) -> Self {
+        let degree = self.degree();
+        if !self.basis_function.knots().is_clamped(degree) {
+            return self.clamp().elevate_degree(t);
+        }
+        let (knots, control_points) =
+            curve_degree_elevate(degree, self.basis_function.knots(), &self.control_points, t);
+        Self {
+            basis_function: BsplineBasis::new(degree + t, knots),
+            control_points,
+        }
+    }
+
+    /// decompose curve into rational bezier segments,
+    /// the i-th segment is on the i-th nonempty knot span \[u_i, u_i+1] of the domain,
+    /// an unclamped curve is clamped first
+    pub fn decompose(&self) -> Vec<RationalBezierCurveBase<P>> {
+        if !self.basis_function.knots().is_clamped(self.degree()) {
+            return self.clamp().decompose();
+        }
+        let breakpoints = self.basis_function.knots().breakpoints(self.degree());
+        curve_decompose(
+            self.degree(),
+            self.basis_function.knots(),
+            &self.control_points,
+        )
+        .into_iter()
+        .zip(breakpoints.windows(2))
+        .map(|(segment, span)| {
+            RationalBezierCurveBase::from_basis(
+                BernsteinBasis::with_len_on(segment.len(), span[0], span[1]),
+                segment,
+            )
+        })
+        .collect()
+    }
+
+    /// split curve at u into two curves on \[u_p, u] and \[u, u_m-p]
+    ///
+    /// error if u is not inside the domain of curve
+    pub fn split_at(&self, u: f64) -> Result<(Self, Self), BasisFunctionError> {
+        let degree = self.degree();
+        let ((left_knots, left), (right_knots, right)) =
+            curve_split(degree, self.basis_function.knots(), &self.control_points, u)?;
+        Ok((
+            Self {
+                basis_function: BsplineBasis::new(degree, left_knots),
+                control_points: left,
+            },
+            Self {
+                basis_function: BsplineBasis::new(degree, right_knots),
+                control_points: right,
+            },
+        ))
+    }
+
+    /// extract the part of curve on \[u0, u1]
+    ///
+    /// error if u1 <= u0 or \[u0, u1] is not inside the domain of curve
+    pub fn sub_curve(&self, u0: f64, u1: f64) -> Result<Self, BasisFunctionError> {
+        let (start, end) = self.domain();
+        if u1 <= u0 {
+            return Err(BasisFunctionError::EmptyDomain);
+        }
+        if u0 < start {
+            return Err(BasisFunctionError::ULessThanMin);
+        }
+        if u1 > end {
+            return Err(BasisFunctionError::UGreaterThanMax);
+        }
+        let right = if u0 > start {
+            self.split_at(u0)?.1
+        } else {
+            self.clone()
+        };
+        if u1 < end {
+            Ok(right.split_at(u1)?.0)
+        } else {
+            Ok(right)
+        }
+    }
+
+    /// same curve on domain \[a, b], knots are mapped affinely
+    ///
+    /// error if a >= b or a, b is not finite
+    pub fn reparametrize(&self, a: f64, b: f64) -> Result<Self, BasisFunctionError> {
+        let degree = self.degree();
+        let knots = self.basis_function.knots().reparametrize(degree, a, b)?;
+        Ok(Self::from_basis(
+            BsplineBasis::new(degree, knots),
+            self.control_points.clone(),
+        ))
+    }
+
+    /// same curve traversed backwards on the same domain
+    pub fn reverse(&self) -> Self {
+        let degree = self.degree();
+        Self::from_basis(
+            BsplineBasis::new(degree, self.basis_function.knots().reverse(degree)),
+            self.control_points.iter().rev().copied().collect(),
+        )
+    }
+
+    /// same curve on clamped knots, it passes through its first and last control point,
+    /// domain is unchanged, e.g. to convert a periodic curve,
+    /// knot insertion is done on homogeneous control points
+    pub fn clamp(&self) -> Self {
+        let (knots, control_points) = curve_clamp(
+            self.degree(),
+            self.basis_function.knots(),
+            &self.control_points,
+        );
+        Self::from_basis(BsplineBasis::new(self.degree(), knots), control_points)
+    }
+
+    /// same curve on unclamped knots (NURBS Book A12.1) on homogeneous control points,
+    /// a closed curve with C^(p-1) continuity at the seam becomes periodic:
+    /// its first degree control points equal the last ones
+    ///
+    /// error if degree is 0 or curve is not clamped
+    pub fn unclamp(&self) -> Result<Self, BasisFunctionError> {
+        let degree = self.degree();
+        if degree == 0 {
+            return Err(BasisFunctionError::TooSmallDegree);
+        }
+        if !self.basis_function.knots().is_clamped(degree) {
+            return Err(BasisFunctionError::NotClamped);
+        }
+        let (knots, control_points) =
+            curve_unclamp(degree, self.basis_function.knots(), &self.control_points);
+        Ok(Self::from_basis(
+            BsplineBasis::new(degree, knots),
+            control_points,
+        ))
+    }
+}
+
+impl<P> NurbsCurveBase<P>
+where
+    P: ControlPoint,
+{
+    /// exact circle of radius around center on domain \[0, 1], starts from center + radius * x_axis
+    /// and runs toward y_axis, x_axis and y_axis should be orthonormal
+    ///
+    /// error if radius is not positive
+    pub fn circle(
+        center: P,
+        x_axis: P,
+        y_axis: P,
+        radius: f64,
+    ) -> Result<Self, BasisFunctionError> {
+        Self::elliptic_arc(center, x_axis, y_axis, radius, radius, 0.0, TAU)
+    }
+
+    /// exact circular arc center + radius * (cos(θ) x_axis + sin(θ) y_axis) on domain \[0, 1]
+    /// for θ from start_angle to end_angle in radians, x_axis and y_axis should be orthonormal
+    ///
+    /// error if radius is not positive or end_angle - start_angle is not in (0, 2π]
+    pub fn arc(
+        center: P,
+        x_axis: P,
+        y_axis: P,
+        radius: f64,
+        start_angle: f64,
+        end_angle: f64,
+    ) -> Result<Self, BasisFunctionError> {
+        Self::elliptic_arc(
+            center,
+            x_axis,
+            y_axis,
+            radius,
+            radius,
+            start_angle,
+            end_angle,
+        )
+    }
+
+    /// exact ellipse with semi axes x_radius * x_axis and y_radius * y_axis on domain \[0, 1],
+    /// x_axis and y_axis should be orthonormal
+    ///
+    /// error if a radius is not positive
+    pub fn ellipse(
+        center: P,
+        x_axis: P,
+        y_axis: P,
+        x_radius: f64,
+        y_radius: f64,
+    ) -> Result<Self, BasisFunctionError> {
+        Self::elliptic_arc(center, x_axis, y_axis, x_radius, y_radius, 0.0, TAU)
+    }
+
+    /// exact elliptic arc center + x_radius cos(θ) x_axis + y_radius sin(θ) y_axis
+    /// on domain \[0, 1] for θ from start_angle to end_angle in radians (NURBS Book A7.1),
+    /// x_axis and y_axis should be orthonormal
+    ///
+    /// error if a radius is not positive or end_angle - start_angle is not in (0, 2π]
+    pub fn elliptic_arc(
+        center: P,
+        x_axis: P,
+        y_axis: P,
+        x_radius: f64,
+        y_radius: f64,
+        start_angle: f64,
+        end_angle: f64,
+    ) -> Result<Self, BasisFunctionError> {
+        let positive = |r: f64| r.is_finite() && r > 0.0;
+        let sweep = end_angle - start_angle;
+        // tolerate rounding of angles which sweep a full turn
+        if !positive(x_radius)
+            || !positive(y_radius)
+            || !positive(sweep)
+            || sweep > TAU * (1.0 + f64::EPSILON)
+        {
+            return Err(BasisFunctionError::DegenerateConic);
+        }
+        let (knots, control_points) = elliptic_arc(
+            center,
+            x_axis,
+            y_axis,
+            x_radius,
+            y_radius,
+            start_angle,
+            end_angle,
+        );
+        Ok(Self::from_basis(
+            BsplineBasis::new(2, knots),
+            control_points,
+        ))
+    }
+}
+
+impl<P> NurbsCurveBase<P>
+where
+    P: ControlPoint + InnerProductSpace,
+{
+    /// exact circular arc on domain \[0, 1] which starts at p0, passes through p1 and ends at p2
+    ///
+    /// error if the points are collinear or coincide
+    pub fn arc_through_points(p0: P, p1: P, p2: P) -> Result<Self, BasisFunctionError> {
+        // circumcenter p2 + s a + t b is equidistant to p0, p1 and p2
+        let (a, b) = (p0 - p2, p1 - p2);
+        let (aa, ab, bb) = (a.dot(&a), a.dot(&b), b.dot(&b));
+        let det = 2.0 * (aa * bb - ab * ab);
+        if det <= INTERSECTION_TOLERANCE * aa * bb {
+            return Err(BasisFunctionError::DegenerateConic);
+        }
+        let (s, t) = (bb * (aa - ab) / det, aa * (bb - ab) / det);
+        let center = p2 + a * s + b * t;
+        let radius = p0.distance(&center);
+        let x_axis = (p0 - center) / radius;
+        // y axis from the point whose direction is further from x axis
+        let (d1, d2) = (p1 - center, p2 - center);
+        let (o1, o2) = (d1 - x_axis * x_axis.dot(&d1), d2 - x_axis * x_axis.dot(&d2));
+        let o = if o1.norm() >= o2.norm() { o1 } else { o2 };
+        let mut y_axis = o / o.norm();
+        let angle = |y_axis: P, d: P| y_axis.dot(&d).atan2(x_axis.dot(&d)).rem_euclid(TAU);
+        if angle(y_axis, d1) > angle(y_axis, d2) {
+            // run the other way round so that p1 lies between p0 and p2
+            y_axis = y_axis * -1.0;
+        }
+        let end_angle = angle(y_axis, d2);
+        let mut curve = Self::arc(center, x_axis, y_axis, radius, 0.0, end_angle)?;
+        // end exactly on given points
+        let last = curve.control_points.len() - 1;
+        curve.control_points[0] = HomoControlPoint::from_control_point(p0, 1.0);
+        curve.control_points[last] = HomoControlPoint::from_control_point(p2, 1.0);
+        Ok(curve)
+    }
+
+    /// exact conic arc on domain \[0, 1] from p0 to p2 with end tangents t0 and t2
+    /// which passes through p (NURBS Book A7.3), it is split into 1, 2 or 4 rational quadratic
+    /// segments so that every weight is positive
+    ///
+    /// error if p0, p, p2 and the tangents do not define a conic arc
+    pub fn conic_arc(p0: P, t0: P, p2: P, t2: P, p: P) -> Result<Self, BasisFunctionError> {
+        let (knots, control_points) = conic_arc(p0, t0, p2, t2, p)?;
+        Ok(Self::from_basis(
+            BsplineBasis::new(2, knots),
+            control_points,
+        ))
+    }
+
+    /// try to remove knot u `times` times, a removal is accepted only if
+    /// the curve deviates from original one no more than tolerance
+    ///
+    /// removal is done on homogeneous control points,
+    /// tolerance is converted by min weight and max distance of control points from origin
+    ///
+    /// output: (new curve, number of times u was actually removed, max deviation)
+    ///
+    /// error if u is not an interior knot
+    pub fn remove_knot(
+        &self,
+        u: f64,
+        times: usize,
+        tolerance: f64,
+    ) -> Result<(Self, usize, f64), BasisFunctionError> {
+        let scale = self.homogeneous_tolerance_scale();
+        let degree = self.degree();
+        let (knots, control_points, removed, deviation) = curve_knot_remove(
+            degree,
+            self.basis_function.knots(),
+            &self.control_points,
+            u,
+            times,
+            tolerance * scale,
+        )?;
+        Ok((
+            Self {
+                basis_function: BsplineBasis::new(degree, knots),
+                control_points,
+            },
+            removed,
+            deviation / scale,
+        ))
+    }
+
+    /// reduce degree from p to p - 1 if the reduced curve deviates from original one
+    /// no more than tolerance, an unclamped curve is clamped first
+    ///
+    /// reduction is done on homogeneous control points,
+    /// tolerance is converted by min weight and max distance of control points from origin
+    ///
+    /// error with the max deviation bound if curve is not degree reducible
+    pub fn reduce_degree(&self, tolerance: f64) -> Result<Self, BasisFunctionError> {
+        if !self.basis_function.knots().is_clamped(self.degree()) {
+            return self.clamp().reduce_degree(tolerance);
+        }
+        let scale = self.homogeneous_tolerance_scale();
+        let degree = self.degree();
+        let (knots, control_points, deviation) =
+            curve_degree_reduce(degree, self.basis_function.knots(), &self.control_points)?;
+        if deviation > tolerance * scale {
+            return Err(BasisFunctionError::NotDegreeReducible(deviation / scale));
+        }
+        Ok(Self {
+            basis_function: BsplineBasis::new(degree - 1, knots),
+            control_points,
+        })
+    }
+
+    /// tolerance in homogeneous space = tolerance * w_min / (1 + |P|_max)
+    fn homogeneous_tolerance_scale(&self) -> f64 {
+        let (w_min, p_max) = self
+            .control_points
+            .iter()
+            .map(|hp| hp.to_control_point_and_weight())
+            .fold((f64::INFINITY, 0.0f64), |(w_min, p_max), (p, w)| {
+                (w_min.min(w), p_max.max(p.norm()))
+            });
+        w_min / (1.0 + p_max)
+    }
 }
 
 impl<P> ParametricCurve<HomoControlPoint<P>> for NurbsCurveBase<P>
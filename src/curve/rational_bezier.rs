@@ -0,0 +1,172 @@
+use crate::{
+    basics::{
+        algorithm::{bezier_degree_elevate, de_casteljaul, de_casteljaul_split},
+        ControlPoint, HomoControlPoint,
+    },
+    basis_function::{BasisFunctionError, BernsteinBasis},
+};
+
+use super::{check_weights, ParametricCurve, RationalCurve};
+
+#[derive(Debug, Clone)]
+pub struct RationalBezierCurveBase<P>
+where
+    P: ControlPoint,
+{
+    basis_function: BernsteinBasis,
+    control_points: Vec<HomoControlPoint<P>>,
+}
+
+impl<P> RationalBezierCurveBase<P>
+where
+    P: ControlPoint,
+{
+    pub fn new(control_points_weights: Vec<(P, f64)>) -> Self {
+        let control_points = control_points_weights
+            .iter()
+            .map(|(p, w)| HomoControlPoint::<P>::from_control_point(*p, *w))
+            .collect::<Vec<_>>();
+        Self::from_homo_control_points(control_points)
+    }
+
+    /// error if control_points_weights is empty or a weight is not positive
+    pub fn try_new(control_points_weights: Vec<(P, f64)>) -> Result<Self, BasisFunctionError> {
+        if control_points_weights.is_empty() {
+            return Err(BasisFunctionError::TooFewPoints);
+        }
+        check_weights(control_points_weights.iter().map(|(_, w)| *w))?;
+        Ok(Self::new(control_points_weights))
+    }
+
+    pub(crate) fn from_homo_control_points(control_points: Vec<HomoControlPoint<P>>) -> Self {
+        Self {
+            basis_function: BernsteinBasis::new(control_points.len()),
+            control_points,
+        }
+    }
+
+    pub(crate) fn from_basis(
+        basis_function: BernsteinBasis,
+        control_points: Vec<HomoControlPoint<P>>,
+    ) -> Self {
+        Self {
+            basis_function,
+            control_points,
+        }
+    }
+
+    /// curve on domain \[a, b] instead of \[0, 1]
+    ///
+    /// error if control_points_weights is empty, a weight is not positive,
+    /// a >= b or a, b is not finite
+    pub fn new_on(
+        control_points_weights: Vec<(P, f64)>,
+        a: f64,
+        b: f64,
+    ) -> Result<Self, BasisFunctionError> {
+        Self::try_new(control_points_weights)?.reparametrize(a, b)
+    }
+
+    /// curve on the same domain with other homogeneous control points
+    fn with_homo_control_points(&self, control_points: Vec<HomoControlPoint<P>>) -> Self {
+        Self {
+            basis_function: self.basis_function.with_len(control_points.len()),
+            control_points,
+        }
+    }
+
+    /// raise degree from p to p + t without changing the shape of curve,
+    /// degree elevation is done on homogeneous control points
+    pub fn elevate_degree(&self, t: usize) -> Self {
+        self.with_homo_control_points(bezier_degree_elevate(&self.control_points, t))
+    }
+
+    /// split curve at u into two curves on \[a, u] and \[u, b], \[a, b] is the domain of curve
+    ///
+    /// error if u is not inside the domain
+    pub fn split_at(&self, u: f64) -> Result<(Self, Self), BasisFunctionError> {
+        let (a, b) = self.domain();
+        if u <= a {
+            return Err(BasisFunctionError::ULessThanMin);
+        }
+        if u >= b {
+            return Err(BasisFunctionError::UGreaterThanMax);
+        }
+        let t = self.basis_function.to_unit(u);
+        let (left, right) = de_casteljaul_split(self.degree(), t, &self.control_points);
+        Ok((
+            Self::from_basis(BernsteinBasis::with_len_on(left.len(), a, u), left),
+            Self::from_basis(BernsteinBasis::with_len_on(right.len(), u, b), right),
+        ))
+    }
+
+    /// extract the part of curve on \[u0, u1]
+    ///
+    /// error if u1 <= u0 or \[u0, u1] is not inside the domain
+    pub fn sub_curve(&self, u0: f64, u1: f64) -> Result<Self, BasisFunctionError> {
+        let (a, b) = self.domain();
+        if u1 <= u0 {
+            return Err(BasisFunctionError::EmptyDomain);
+        }
+        if u0 < a {
+            return Err(BasisFunctionError::ULessThanMin);
+        }
+        if u1 > b {
+            return Err(BasisFunctionError::UGreaterThanMax);
+        }
+        let left = if u1 < b {
+            self.split_at(u1)?.0
+        } else {
+            self.clone()
+        };
+        if u0 > a {
+            Ok(left.split_at(u0)?.1)
+        } else {
+            Ok(left)
+        }
+    }
+
+    /// same curve on domain \[a, b]
+    ///
+    /// error if a >= b or a, b is not finite
+    pub fn reparametrize(&self, a: f64, b: f64) -> Result<Self, BasisFunctionError> {
+        Ok(Self {
+            basis_function: BernsteinBasis::new_on(self.control_points.len(), a, b)?,
+            control_points: self.control_points.clone(),
+        })
+    }
+
+    /// same curve traversed backwards on the same domain
+    pub fn reverse(&self) -> Self {
+        self.with_homo_control_points(self.control_points.iter().rev().copied().collect())
+    }
+}
+
+impl<P> ParametricCurve<HomoControlPoint<P>> for RationalBezierCurveBase<P>
+where
+    P: ControlPoint,
+{
+    type BasisFunction = BernsteinBasis;
+    fn basis_function(&self) -> &Self::BasisFunction {
+        &self.basis_function
+    }
+    fn control_points(&self) -> &Vec<HomoControlPoint<P>> {
+        &self.control_points
+    }
+}
+
+impl<P> RationalCurve<P> for RationalBezierCurveBase<P>
+where
+    P: ControlPoint,
+{
+    /// use deCasteljaul algorithm on homogeneous control points
+    fn get_point(&self, u: f64) -> P {
+        de_casteljaul(
+            self.degree(),
+            self.basis_function.to_unit(u),
+            &self.control_points,
+        )
+        .to_control_point_and_weight()
+        .0
+    }
+}
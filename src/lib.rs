@@ -1,9 +1,10 @@
-mod basics;
+pub mod basics;
 /**
  * # niubi
  *  A rust lib of freeform curves and surfaces
  */
-mod basis_function;
+pub mod basis_function;
+pub mod builtin;
 pub mod curve;
 pub mod surface;
 
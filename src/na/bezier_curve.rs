@@ -36,7 +36,7 @@ fn test_bezier_curve_basis_get_point() {
         Vector2::new(600.0, 300.0),
     ]);
 
-    let ans = vec![
+    let ans = [
         Vector2::new(232.80000000000004, 229.80000000000007),
         Vector2::new(311.59999999999997, 284.6),
         Vector2::new(400.0, 325.0),
@@ -62,7 +62,7 @@ fn test_bezier_curve_basis_get_ders() {
         Vector2::new(500.0, 400.0),
         Vector2::new(600.0, 300.0),
     ]);
-    let ans = vec![
+    let ans = [
         Vector2::new(232.8, 229.8),
         Vector2::new(354.0, 294.0),
         Vector2::new(480.0, -120.0),
@@ -73,3 +73,137 @@ fn test_bezier_curve_basis_get_ders() {
         assert_approx_eq!(r.y, a.y);
     }
 }
+
+#[test]
+fn test_bezier_curve_elevate_degree() {
+    use crate::curve::{NonRationalCurve, ParametricCurve};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    let bezier = BezierCurve::new(vec![
+        Vector2::new(0.0, 0.0),
+        Vector2::new(1.0, 2.0),
+        Vector2::new(3.0, 0.0),
+    ]);
+    let elevated = bezier.elevate_degree(1);
+    assert_eq!(elevated.degree(), 3);
+    let ans = [
+        Vector2::new(0.0, 0.0),
+        Vector2::new(2.0 / 3.0, 4.0 / 3.0),
+        Vector2::new(5.0 / 3.0, 4.0 / 3.0),
+        Vector2::new(3.0, 0.0),
+    ];
+    for (r, a) in elevated.control_points().iter().zip(ans.iter()) {
+        assert_approx_eq!(r.x, a.x);
+        assert_approx_eq!(r.y, a.y);
+    }
+    let elevated = bezier.elevate_degree(3);
+    assert_eq!(elevated.degree(), 5);
+    for i in 0..=10 {
+        let u = i as f64 / 10.0;
+        let a = bezier.get_point(u);
+        let b = elevated.get_point(u);
+        assert_approx_eq!(a.x, b.x);
+        assert_approx_eq!(a.y, b.y);
+    }
+}
+
+#[test]
+fn test_bezier_curve_split() {
+    use crate::basis_function::BasisFunctionError;
+    use crate::curve::NonRationalCurve;
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    let bezier = BezierCurve::new(vec![
+        Vector2::new(200.0, 200.0),
+        Vector2::new(300.0, 300.0),
+        Vector2::new(500.0, 400.0),
+        Vector2::new(600.0, 300.0),
+    ]);
+    let (left, right) = bezier.split_at(0.3).unwrap();
+    let sub = bezier.sub_curve(0.2, 0.7).unwrap();
+    for i in 0..=10 {
+        let t = i as f64 / 10.0;
+        for (a, b) in [
+            (bezier.get_point(0.3 * t), left.get_point(0.3 * t)),
+            (
+                bezier.get_point(0.3 + 0.7 * t),
+                right.get_point(0.3 + 0.7 * t),
+            ),
+            (
+                bezier.get_point(0.2 + 0.5 * t),
+                sub.get_point(0.2 + 0.5 * t),
+            ),
+        ] {
+            assert_approx_eq!(a.x, b.x);
+            assert_approx_eq!(a.y, b.y);
+        }
+    }
+    assert!(bezier.split_at(0.0).is_err());
+    assert!(bezier.split_at(1.0).is_err());
+    assert!(matches!(
+        bezier.sub_curve(0.7, 0.2),
+        Err(BasisFunctionError::EmptyDomain)
+    ));
+    assert!(bezier.sub_curve(0.2, 1.2).is_err());
+}
+
+#[test]
+fn test_bezier_curve_domain() {
+    use crate::basis_function::BasisFunctionError;
+    use crate::curve::{NonRationalCurve, ParametricCurve};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    let control_points = vec![
+        Vector2::new(200.0, 200.0),
+        Vector2::new(300.0, 300.0),
+        Vector2::new(500.0, 400.0),
+        Vector2::new(600.0, 300.0),
+    ];
+    let bezier = BezierCurve::new(control_points.clone());
+    let moved = BezierCurve::new_on(control_points, 2.5, 17.0).unwrap();
+    assert_eq!(bezier.domain(), (0.0, 1.0));
+    assert_eq!(moved.domain(), (2.5, 17.0));
+    assert_eq!(
+        bezier.reparametrize(2.5, 17.0).unwrap().domain(),
+        (2.5, 17.0)
+    );
+    let reversed = moved.reverse();
+    assert_eq!(reversed.domain(), (2.5, 17.0));
+    let (left, right) = moved.split_at(10.0).unwrap();
+    assert_eq!(left.domain(), (2.5, 10.0));
+    assert_eq!(right.domain(), (10.0, 17.0));
+    let sub = moved.sub_curve(5.0, 12.0).unwrap();
+    assert_eq!(sub.domain(), (5.0, 12.0));
+    for i in 0..=10 {
+        let t = i as f64 / 10.0;
+        let u = 2.5 + 14.5 * t;
+        let ders = bezier.get_ders(1, t);
+        let moved_ders = moved.get_ders(1, u);
+        assert_approx_eq!(ders[0].x, moved.get_point(u).x);
+        assert_approx_eq!(ders[0].y, moved.get_point(u).y);
+        assert_approx_eq!(ders[0].x, moved_ders[0].x);
+        assert_approx_eq!(ders[1].y / 14.5, moved_ders[1].y);
+        for (a, b) in [
+            (moved.get_point(u), reversed.get_point(19.5 - u)),
+            (
+                moved.get_point(2.5 + 7.5 * t),
+                left.get_point(2.5 + 7.5 * t),
+            ),
+            (
+                moved.get_point(10.0 + 7.0 * t),
+                right.get_point(10.0 + 7.0 * t),
+            ),
+            (moved.get_point(5.0 + 7.0 * t), sub.get_point(5.0 + 7.0 * t)),
+        ] {
+            assert_approx_eq!(a.x, b.x);
+            assert_approx_eq!(a.y, b.y);
+        }
+    }
+    assert!(moved.split_at(1.0).is_err());
+    assert!(moved.sub_curve(1.0, 5.0).is_err());
+    assert!(BezierCurve::new_on(vec![Vector2::zeros()], 1.0, 1.0).is_err());
+    assert!(matches!(
+        BezierCurve::<2>::new_on(vec![], 0.0, 1.0),
+        Err(BasisFunctionError::TooFewPoints)
+    ));
+}
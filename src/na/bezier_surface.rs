@@ -37,3 +37,186 @@ fn test_bezier_surface_get_point() {
         Vector4::new(0., 0.75, 1., 1.25)
     );
 }
+
+#[test]
+fn test_bezier_surface_get_point_non_square() {
+    use crate::surface::NonRationalSurface;
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector3;
+    // p = 1 < q = 2, columns of control net are evaluated in u first
+    let control_points = vec![
+        vec![
+            Vector3::new(0., 0., 0.),
+            Vector3::new(0., 1., 2.),
+            Vector3::new(0., 2., 0.),
+        ],
+        vec![
+            Vector3::new(1., 0., 1.),
+            Vector3::new(1., 1., -1.),
+            Vector3::new(1., 2., 3.),
+        ],
+    ];
+    let surface = BezierSurface::new(control_points.clone());
+    for (u, v) in [(0.0, 0.0), (0.3, 0.6), (0.5, 0.5), (1.0, 0.2)] {
+        let bu = [1.0 - u, u];
+        let bv = [(1.0 - v) * (1.0 - v), 2.0 * v * (1.0 - v), v * v];
+        let mut expected = Vector3::zeros();
+        for (row, bu) in control_points.iter().zip(bu) {
+            for (cp, bv) in row.iter().zip(bv) {
+                expected += cp * bu * bv;
+            }
+        }
+        let point = surface.get_point(u, v);
+        assert_approx_eq!(point.x, expected.x);
+        assert_approx_eq!(point.y, expected.y);
+        assert_approx_eq!(point.z, expected.z);
+    }
+}
+
+#[test]
+fn test_bezier_surface_elevate_degree() {
+    use crate::surface::{NonRationalSurface, ParametricSurface};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector3;
+    let surface = BezierSurface::new(vec![
+        vec![Vector3::new(0., 0., 0.), Vector3::new(0., 1., 1.)],
+        vec![Vector3::new(1., 0., 2.), Vector3::new(1., 1., -1.)],
+        vec![Vector3::new(2., 0., 0.), Vector3::new(2., 1., 1.)],
+    ]);
+    let elevated = surface.elevate_degree_u(1).elevate_degree_v(2);
+    assert_eq!(elevated.p(), 3);
+    assert_eq!(elevated.q(), 3);
+    for i in 0..=10 {
+        for j in 0..=10 {
+            let (u, v) = (i as f64 / 10.0, j as f64 / 10.0);
+            let a = surface.get_point(u, v);
+            let b = elevated.get_point(u, v);
+            assert_approx_eq!(a.x, b.x);
+            assert_approx_eq!(a.y, b.y);
+            assert_approx_eq!(a.z, b.z);
+        }
+    }
+}
+
+#[test]
+fn test_bezier_surface_split() {
+    use crate::basis_function::BasisFunctionError;
+    use crate::surface::NonRationalSurface;
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector3;
+    let surface = BezierSurface::new(vec![
+        vec![Vector3::new(0., 0., 0.), Vector3::new(0., 1., 1.)],
+        vec![Vector3::new(1., 0., 2.), Vector3::new(1., 1., -1.)],
+        vec![Vector3::new(2., 0., 0.), Vector3::new(2., 1., 1.)],
+    ]);
+    let (bottom, top) = surface.split_u(0.4).unwrap();
+    let (left, right) = surface.split_v(0.7).unwrap();
+    let sub = surface.sub_surface(0.2..0.6, 0.5..1.0).unwrap();
+    for i in 0..=5 {
+        for j in 0..=5 {
+            let (s, t) = (i as f64 / 5.0, j as f64 / 5.0);
+            let (u, v) = (0.2 + 0.4 * s, 0.5 + 0.5 * t);
+            for (a, b) in [
+                (surface.get_point(0.4 * s, t), bottom.get_point(0.4 * s, t)),
+                (
+                    surface.get_point(0.4 + 0.6 * s, t),
+                    top.get_point(0.4 + 0.6 * s, t),
+                ),
+                (surface.get_point(s, 0.7 * t), left.get_point(s, 0.7 * t)),
+                (
+                    surface.get_point(s, 0.7 + 0.3 * t),
+                    right.get_point(s, 0.7 + 0.3 * t),
+                ),
+                (surface.get_point(u, v), sub.get_point(u, v)),
+            ] {
+                assert_approx_eq!(a.x, b.x);
+                assert_approx_eq!(a.y, b.y);
+                assert_approx_eq!(a.z, b.z);
+            }
+        }
+    }
+    assert!(surface.split_u(1.0).is_err());
+    assert!(surface.split_v(0.0).is_err());
+    assert!(matches!(
+        surface.sub_surface(0.5..0.2, 0.0..1.0),
+        Err(BasisFunctionError::EmptyDomain)
+    ));
+}
+
+#[test]
+fn test_bezier_surface_iso_curve() {
+    use crate::curve::{NonRationalCurve, ParametricCurve};
+    use crate::surface::{NonRationalSurface, ParametricSurface};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector3;
+    let surface = BezierSurface::new(vec![
+        vec![Vector3::new(0., 0., 0.), Vector3::new(0., 1., 1.)],
+        vec![Vector3::new(1., 0., 2.), Vector3::new(1., 1., -1.)],
+        vec![Vector3::new(2., 0., 0.), Vector3::new(2., 1., 1.)],
+    ]);
+    for i in 0..=5 {
+        let c = i as f64 / 5.0;
+        let u_curve = surface.iso_curve_u(c).unwrap();
+        let v_curve = surface.iso_curve_v(c).unwrap();
+        assert_eq!(u_curve.degree(), 1);
+        assert_eq!(v_curve.degree(), 2);
+        for j in 0..=5 {
+            let t = j as f64 / 5.0;
+            for (a, b) in [
+                (surface.get_point(c, t), u_curve.get_point(t)),
+                (surface.get_point(t, c), v_curve.get_point(t)),
+            ] {
+                assert_approx_eq!(a.x, b.x);
+                assert_approx_eq!(a.y, b.y);
+                assert_approx_eq!(a.z, b.z);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_bezier_surface_domain() {
+    use crate::curve::{NonRationalCurve, ParametricCurve};
+    use crate::surface::{NonRationalSurface, ParametricSurface};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector3;
+    let surface = BezierSurface::new(vec![
+        vec![Vector3::new(0., 0., 0.), Vector3::new(0., 1., 1.)],
+        vec![Vector3::new(1., 0., 2.), Vector3::new(1., 1., -1.)],
+        vec![Vector3::new(2., 0., 0.), Vector3::new(2., 1., 1.)],
+    ]);
+    let moved = surface.reparametrize(1.0..3.0, -2.0..0.0).unwrap();
+    assert_eq!(moved.domain(), ((1.0, 3.0), (-2.0, 0.0)));
+    let reversed = moved.reverse_u().reverse_v();
+    let sub = moved.sub_surface(1.5..2.5, -1.0..0.0).unwrap();
+    assert_eq!(sub.domain(), ((1.5, 2.5), (-1.0, 0.0)));
+    let (bottom, top) = moved.split_u(2.0).unwrap();
+    assert_eq!(bottom.domain(), ((1.0, 2.0), (-2.0, 0.0)));
+    assert_eq!(top.domain(), ((2.0, 3.0), (-2.0, 0.0)));
+    let (left, right) = moved.split_v(-0.5).unwrap();
+    assert_eq!(left.domain(), ((1.0, 3.0), (-2.0, -0.5)));
+    assert_eq!(right.domain(), ((1.0, 3.0), (-0.5, 0.0)));
+    for i in 0..=5 {
+        let s = i as f64 / 5.0;
+        let u = 1.0 + 2.0 * s;
+        let iso = moved.iso_curve_u(u).unwrap();
+        assert_eq!(iso.domain(), (-2.0, 0.0));
+        for j in 0..=5 {
+            let t = j as f64 / 5.0;
+            let v = -2.0 + 2.0 * t;
+            for (a, b) in [
+                (surface.get_point(s, t), moved.get_point(u, v)),
+                (moved.get_point(u, v), reversed.get_point(4.0 - u, -2.0 - v)),
+                (moved.get_point(u, v), iso.get_point(v)),
+                (
+                    moved.get_point(1.5 + s, -1.0 + t),
+                    sub.get_point(1.5 + s, -1.0 + t),
+                ),
+            ] {
+                assert_approx_eq!(a.x, b.x);
+                assert_approx_eq!(a.y, b.y);
+                assert_approx_eq!(a.z, b.z);
+            }
+        }
+    }
+}
@@ -52,3 +52,800 @@ fn test_bsplie_curve_basis_get_ders() {
     assert_eq!(ders[1], Vector2::new(400.0000000000001, 360.0000000000001));
     assert_eq!(ders[2], Vector2::new(0.0, -400.0));
 }
+
+#[test]
+fn test_bspline_curve_insert_knot() {
+    use crate::basis_function::Basis;
+    use crate::curve::{NonRationalCurve, ParametricCurve};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    let bspline = BsplineCurve::new(
+        vec![
+            Vector2::new(200.0, 200.0),
+            Vector2::new(300.0, 300.0),
+            Vector2::new(500.0, 400.0),
+            Vector2::new(600.0, 300.0),
+            Vector2::new(700.0, 100.0),
+        ],
+        vec![0.0, 0.0, 0.0, 0.0, 0.5, 1.0, 1.0, 1.0, 1.0],
+        3,
+    );
+    for (u, times) in [(0.25, 1), (0.25, 3), (0.5, 2), (0.75, 2)] {
+        let refined = bspline.insert_knot(u, times).unwrap();
+        assert_eq!(
+            refined.control_points().len(),
+            bspline.control_points().len() + times
+        );
+        assert_eq!(
+            refined.basis_function().knots().multiplicity(u),
+            bspline.basis_function().knots().multiplicity(u) + times
+        );
+        for i in 0..=20 {
+            let t = i as f64 / 20.0;
+            let a = bspline.get_point(t);
+            let b = refined.get_point(t);
+            assert_approx_eq!(a.x, b.x);
+            assert_approx_eq!(a.y, b.y);
+        }
+    }
+    assert!(bspline.insert_knot(0.5, 3).is_err());
+    assert!(bspline.insert_knot(0.0, 1).is_err());
+    assert!(bspline.insert_knot(1.5, 1).is_err());
+}
+
+#[test]
+fn test_bspline_curve_refine_knots() {
+    use crate::basis_function::Basis;
+    use crate::curve::{NonRationalCurve, ParametricCurve};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    let bspline = BsplineCurve::new(
+        vec![
+            Vector2::new(200.0, 200.0),
+            Vector2::new(300.0, 300.0),
+            Vector2::new(500.0, 400.0),
+            Vector2::new(600.0, 300.0),
+            Vector2::new(700.0, 100.0),
+        ],
+        vec![0.0, 0.0, 0.0, 0.0, 0.5, 1.0, 1.0, 1.0, 1.0],
+        3,
+    );
+    let x = [0.1, 0.25, 0.25, 0.5, 0.6, 0.9];
+    let refined = bspline.refine_knots(&x).unwrap();
+    assert_eq!(
+        refined.basis_function().knots().0,
+        vec![0.0, 0.0, 0.0, 0.0, 0.1, 0.25, 0.25, 0.5, 0.5, 0.6, 0.9, 1.0, 1.0, 1.0, 1.0]
+    );
+    assert_eq!(refined.control_points().len(), 11);
+    let single = bspline
+        .insert_knot(0.25, 2)
+        .and_then(|c| c.insert_knot(0.5, 1))
+        .unwrap();
+    let refined_part = bspline.refine_knots(&[0.25, 0.25, 0.5]).unwrap();
+    for (a, b) in single
+        .control_points()
+        .iter()
+        .zip(refined_part.control_points())
+    {
+        assert_approx_eq!(a.x, b.x);
+        assert_approx_eq!(a.y, b.y);
+    }
+    for i in 0..=20 {
+        let t = i as f64 / 20.0;
+        let a = bspline.get_point(t);
+        let b = refined.get_point(t);
+        assert_approx_eq!(a.x, b.x);
+        assert_approx_eq!(a.y, b.y);
+    }
+    assert!(bspline.refine_knots(&[0.5, 0.25]).is_err());
+    assert!(bspline.refine_knots(&[0.5, 1.5]).is_err());
+}
+
+#[test]
+fn test_bspline_curve_remove_knot() {
+    use crate::basis_function::Basis;
+    use crate::curve::{NonRationalCurve, ParametricCurve};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    let bspline = BsplineCurve::new(
+        vec![
+            Vector2::new(200.0, 200.0),
+            Vector2::new(300.0, 300.0),
+            Vector2::new(500.0, 400.0),
+            Vector2::new(600.0, 300.0),
+            Vector2::new(700.0, 100.0),
+        ],
+        vec![0.0, 0.0, 0.0, 0.0, 0.5, 1.0, 1.0, 1.0, 1.0],
+        3,
+    );
+    // knots added by insertion can be removed exactly
+    let refined = bspline.insert_knot(0.3, 2).unwrap();
+    let (removed, times, deviation) = refined.remove_knot(0.3, 2, 1e-9).unwrap();
+    assert_eq!(times, 2);
+    assert!(deviation < 1e-9);
+    assert_eq!(
+        removed.basis_function().knots().0,
+        bspline.basis_function().knots().0
+    );
+    for (a, b) in removed.control_points().iter().zip(bspline.control_points()) {
+        assert_approx_eq!(a.x, b.x);
+        assert_approx_eq!(a.y, b.y);
+    }
+    let refined = bspline.insert_knot(0.5, 2).unwrap();
+    let (removed, times, _) = refined.remove_knot(0.5, 3, 1e-9).unwrap();
+    assert_eq!(times, 2);
+    assert_eq!(removed.control_points().len(), 5);
+    for i in 0..=20 {
+        let t = i as f64 / 20.0;
+        let a = bspline.get_point(t);
+        let b = removed.get_point(t);
+        assert_approx_eq!(a.x, b.x);
+        assert_approx_eq!(a.y, b.y);
+    }
+
+    // original knot changes the shape, it can not be removed with small tolerance
+    let (same, times, deviation) = bspline.remove_knot(0.5, 1, 1e-3).unwrap();
+    assert_eq!(times, 0);
+    assert_eq!(deviation, 0.0);
+    assert_eq!(same.control_points().len(), 5);
+    let (_, times, deviation) = bspline.remove_knot(0.5, 1, 1e3).unwrap();
+    assert_eq!(times, 1);
+    assert!(deviation > 1e-3);
+
+    assert!(bspline.remove_knot(0.25, 1, 1.0).is_err());
+    assert!(bspline.remove_knot(0.0, 1, 1.0).is_err());
+    assert!(bspline.remove_knot(1.0, 1, 1.0).is_err());
+}
+
+#[test]
+fn test_bspline_curve_elevate_degree() {
+    use crate::basis_function::Basis;
+    use crate::curve::{NonRationalCurve, ParametricCurve};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    let bspline = BsplineCurve::new(
+        vec![
+            Vector2::new(200.0, 200.0),
+            Vector2::new(300.0, 300.0),
+            Vector2::new(500.0, 400.0),
+            Vector2::new(600.0, 300.0),
+            Vector2::new(700.0, 100.0),
+            Vector2::new(800.0, 200.0),
+            Vector2::new(900.0, 500.0),
+        ],
+        vec![0.0, 0.0, 0.0, 0.25, 0.5, 0.5, 0.75, 1.0, 1.0, 1.0],
+        2,
+    );
+    for t in 1..=3 {
+        let elevated = bspline.elevate_degree(t);
+        assert_eq!(elevated.degree(), 2 + t);
+        let knots = elevated.basis_function().knots();
+        assert_eq!(knots.multiplicity(0.0), 3 + t);
+        assert_eq!(knots.multiplicity(0.25), 1 + t);
+        assert_eq!(knots.multiplicity(0.5), 2 + t);
+        assert_eq!(knots.multiplicity(1.0), 3 + t);
+        assert_eq!(
+            elevated.control_points().len(),
+            knots.m() - elevated.degree()
+        );
+        for i in 0..=40 {
+            let u = i as f64 / 40.0;
+            let a = bspline.get_point(u);
+            let b = elevated.get_point(u);
+            assert_approx_eq!(a.x, b.x, 1e-9);
+            assert_approx_eq!(a.y, b.y, 1e-9);
+        }
+    }
+    let cubic = BsplineCurve::new_uniform(bspline.control_points().clone(), 3);
+    for t in 1..=2 {
+        let elevated = cubic.elevate_degree(t);
+        assert_eq!(elevated.control_points().len(), 7 + 4 * t);
+        for i in 0..=40 {
+            let u = i as f64 / 40.0;
+            let a = cubic.get_point(u);
+            let b = elevated.get_point(u);
+            assert_approx_eq!(a.x, b.x, 1e-9);
+            assert_approx_eq!(a.y, b.y, 1e-9);
+        }
+    }
+}
+
+#[test]
+fn test_bspline_curve_reduce_degree() {
+    use crate::basis_function::{Basis, BasisFunctionError};
+    use crate::curve::{NonRationalCurve, ParametricCurve};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    let bspline = BsplineCurve::new(
+        vec![
+            Vector2::new(200.0, 200.0),
+            Vector2::new(300.0, 300.0),
+            Vector2::new(500.0, 400.0),
+            Vector2::new(600.0, 300.0),
+            Vector2::new(700.0, 100.0),
+            Vector2::new(800.0, 200.0),
+            Vector2::new(900.0, 500.0),
+        ],
+        vec![0.0, 0.0, 0.0, 0.25, 0.5, 0.5, 0.75, 1.0, 1.0, 1.0],
+        2,
+    );
+    // elevated curve can be reduced back exactly
+    for t in 1..=2 {
+        let mut reduced = bspline.elevate_degree(t);
+        for _ in 0..t {
+            reduced = reduced.reduce_degree(1e-6).unwrap();
+        }
+        assert_eq!(reduced.degree(), 2);
+        assert_eq!(
+            reduced.basis_function().knots().0,
+            bspline.basis_function().knots().0
+        );
+        for (a, b) in reduced.control_points().iter().zip(bspline.control_points()) {
+            assert_approx_eq!(a.x, b.x, 1e-6);
+            assert_approx_eq!(a.y, b.y, 1e-6);
+        }
+    }
+
+    // a real quadratic curve can only be approximated by lines
+    let deviation = match bspline.reduce_degree(1.0) {
+        Err(BasisFunctionError::NotDegreeReducible(deviation)) => deviation,
+        _ => panic!("quadratic curve should not be reducible with small tolerance"),
+    };
+    assert!(deviation > 1.0);
+    let reduced = bspline.reduce_degree(deviation).unwrap();
+    assert_eq!(reduced.degree(), 1);
+    for i in 0..=40 {
+        let u = i as f64 / 40.0;
+        let a = bspline.get_point(u);
+        let b = reduced.get_point(u);
+        assert!((a - b).norm() <= deviation);
+    }
+
+    let cubic = BsplineCurve::new_uniform(bspline.control_points().clone(), 3);
+    let reduced = cubic.elevate_degree(1).reduce_degree(1e-6).unwrap();
+    assert_eq!(reduced.control_points().len(), 7);
+    for (a, b) in reduced.control_points().iter().zip(cubic.control_points()) {
+        assert_approx_eq!(a.x, b.x, 1e-6);
+        assert_approx_eq!(a.y, b.y, 1e-6);
+    }
+
+    let line = BsplineCurve::new_uniform(bspline.control_points().clone(), 1);
+    assert!(matches!(
+        line.reduce_degree(1.0),
+        Err(BasisFunctionError::TooSmallDegree)
+    ));
+}
+
+#[test]
+fn test_bspline_curve_decompose() {
+    use crate::curve::{NonRationalCurve, ParametricCurve};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    let control_points = vec![
+        Vector2::new(200.0, 200.0),
+        Vector2::new(300.0, 300.0),
+        Vector2::new(500.0, 400.0),
+        Vector2::new(600.0, 300.0),
+        Vector2::new(700.0, 100.0),
+        Vector2::new(800.0, 200.0),
+        Vector2::new(900.0, 500.0),
+    ];
+    let bspline = BsplineCurve::new(
+        control_points.clone(),
+        vec![0.0, 0.0, 0.0, 0.25, 0.5, 0.5, 0.75, 1.0, 1.0, 1.0],
+        2,
+    );
+    let cubic = BsplineCurve::new_uniform(control_points, 3);
+    for (curve, spans) in [
+        (bspline, [0.0, 0.25, 0.5, 0.75, 1.0]),
+        (cubic, [0.0, 0.25, 0.5, 0.75, 1.0]),
+    ] {
+        let segments = curve.decompose();
+        assert_eq!(segments.len(), 4);
+        for (k, segment) in segments.iter().enumerate() {
+            assert_eq!(segment.degree(), curve.degree());
+            assert_eq!(segment.domain(), (spans[k], spans[k + 1]));
+            for i in 0..=10 {
+                let t = i as f64 / 10.0;
+                let u = spans[k] + t * (spans[k + 1] - spans[k]);
+                let a = curve.get_point(u);
+                let b = segment.get_point(u);
+                assert_approx_eq!(a.x, b.x);
+                assert_approx_eq!(a.y, b.y);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_bspline_curve_split() {
+    use crate::basis_function::BasisFunctionError;
+    use crate::basis_function::Basis;
+    use crate::curve::{NonRationalCurve, ParametricCurve};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    let bspline = BsplineCurve::new(
+        vec![
+            Vector2::new(200.0, 200.0),
+            Vector2::new(300.0, 300.0),
+            Vector2::new(500.0, 400.0),
+            Vector2::new(600.0, 300.0),
+            Vector2::new(700.0, 100.0),
+            Vector2::new(800.0, 200.0),
+            Vector2::new(900.0, 500.0),
+        ],
+        vec![0.0, 0.0, 0.0, 0.25, 0.5, 0.5, 0.75, 1.0, 1.0, 1.0],
+        2,
+    );
+    for u in [0.1, 0.25, 0.5, 0.6] {
+        let (left, right) = bspline.split_at(u).unwrap();
+        let left_knots = left.basis_function().knots();
+        let right_knots = right.basis_function().knots();
+        assert_eq!(left_knots[0], 0.0);
+        assert_eq!(left_knots[left_knots.m()], u);
+        assert_eq!(right_knots[0], u);
+        assert_eq!(right_knots[right_knots.m()], 1.0);
+        assert_eq!(left.control_points().len(), left_knots.m() - 2);
+        assert_eq!(right.control_points().len(), right_knots.m() - 2);
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            for v in [u * t, u + (1.0 - u) * t] {
+                let a = bspline.get_point(v);
+                let b = if v <= u {
+                    left.get_point(v)
+                } else {
+                    right.get_point(v)
+                };
+                assert_approx_eq!(a.x, b.x);
+                assert_approx_eq!(a.y, b.y);
+            }
+        }
+    }
+    let sub = bspline.sub_curve(0.3, 0.8).unwrap();
+    assert_eq!(sub.basis_function().knots().0[0], 0.3);
+    for i in 0..=10 {
+        let v = 0.3 + 0.05 * i as f64;
+        let a = bspline.get_point(v);
+        let b = sub.get_point(v);
+        assert_approx_eq!(a.x, b.x);
+        assert_approx_eq!(a.y, b.y);
+    }
+    assert!(bspline.split_at(0.0).is_err());
+    assert!(bspline.split_at(1.0).is_err());
+    assert!(matches!(
+        bspline.sub_curve(0.8, 0.3),
+        Err(BasisFunctionError::EmptyDomain)
+    ));
+}
+
+#[test]
+fn test_bspline_curve_interpolate() {
+    use crate::basics::Parameterization;
+    use crate::curve::{NonRationalCurve, ParametricCurve};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector3;
+    let points = vec![
+        Vector3::new(0.0, 0.0, 0.0),
+        Vector3::new(3.0, 4.0, 0.0),
+        Vector3::new(-1.0, 4.0, 1.0),
+        Vector3::new(-4.0, 0.0, 2.0),
+        Vector3::new(-4.0, -3.0, 2.0),
+        Vector3::new(0.0, -5.0, 1.0),
+    ];
+    for parameterization in [
+        Parameterization::Uniform,
+        Parameterization::ChordLength,
+        Parameterization::Centripetal,
+    ] {
+        let params = parameterization.params(&points);
+        for degree in 1..=5 {
+            let curve = BsplineCurve::interpolate(&points, degree, parameterization, None).unwrap();
+            assert_eq!(curve.degree(), degree);
+            assert_eq!(curve.control_points().len(), points.len());
+            for (u, q) in params.iter().zip(&points) {
+                let p = curve.get_point(*u);
+                assert_approx_eq!(p.x, q.x);
+                assert_approx_eq!(p.y, q.y);
+                assert_approx_eq!(p.z, q.z);
+            }
+        }
+    }
+    assert!(BsplineCurve::interpolate(&points, 6, Parameterization::Uniform, None).is_err());
+    assert!(BsplineCurve::interpolate(&points, 0, Parameterization::Uniform, None).is_err());
+}
+
+#[test]
+fn test_bspline_curve_interpolate_end_derivatives() {
+    use crate::basics::Parameterization;
+    use crate::basis_function::{Basis, BasisFunctionError};
+    use crate::curve::{NonRationalCurve, ParametricCurve};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    let points = vec![
+        Vector2::new(0.0, 0.0),
+        Vector2::new(3.0, 4.0),
+        Vector2::new(-1.0, 4.0),
+        Vector2::new(-4.0, 0.0),
+    ];
+    let d0 = Vector2::new(10.0, 0.0);
+    let dn = Vector2::new(0.0, -10.0);
+    let params = Parameterization::ChordLength.params(&points);
+    for degree in 2..=5 {
+        let curve = BsplineCurve::interpolate(
+            &points,
+            degree,
+            Parameterization::ChordLength,
+            Some((d0, dn)),
+        )
+        .unwrap();
+        assert_eq!(curve.control_points().len(), points.len() + 2);
+        for (u, q) in params.iter().zip(&points) {
+            let p = curve.get_point(*u);
+            assert_approx_eq!(p.x, q.x);
+            assert_approx_eq!(p.y, q.y);
+        }
+        for (u, d) in [(0.0, d0), (1.0, dn)] {
+            let ders = curve.get_ders(1, u);
+            assert_approx_eq!(ders[1].x, d.x);
+            assert_approx_eq!(ders[1].y, d.y);
+        }
+    }
+    assert!(matches!(
+        BsplineCurve::interpolate(&points, 6, Parameterization::ChordLength, Some((d0, dn))),
+        Err(BasisFunctionError::TooLargeDegree)
+    ));
+    assert!(matches!(
+        BsplineCurve::interpolate(&points, 1, Parameterization::ChordLength, Some((d0, dn))),
+        Err(BasisFunctionError::TooSmallDegree)
+    ));
+
+    // cubic hermite curve through 2 points
+    let hermite =
+        BsplineCurve::interpolate(&points[..2], 3, Parameterization::Uniform, Some((d0, dn)))
+            .unwrap();
+    assert_eq!(hermite.control_points().len(), 4);
+    assert_eq!(hermite.basis_function().knots().m(), 7);
+    for (u, q, d) in [(0.0, points[0], d0), (1.0, points[1], dn)] {
+        let ders = hermite.get_ders(1, u);
+        assert_approx_eq!(ders[0].x, q.x);
+        assert_approx_eq!(ders[0].y, q.y);
+        assert_approx_eq!(ders[1].x, d.x);
+        assert_approx_eq!(ders[1].y, d.y);
+    }
+}
+
+#[test]
+fn test_bspline_curve_interpolate_singular() {
+    use crate::basics::Parameterization;
+    use crate::basis_function::BasisFunctionError;
+    use nalgebra::Vector2;
+    // repeated point gives two identical rows
+    let points = vec![
+        Vector2::new(0.0, 0.0),
+        Vector2::new(1.0, 1.0),
+        Vector2::new(1.0, 1.0),
+        Vector2::new(2.0, 0.0),
+    ];
+    assert!(matches!(
+        BsplineCurve::interpolate(&points, 2, Parameterization::ChordLength, None),
+        Err(BasisFunctionError::SingularMatrix)
+    ));
+}
+
+#[test]
+fn test_bspline_curve_fit_least_squares() {
+    use crate::basics::Parameterization;
+    use crate::basis_function::BasisFunctionError;
+    use crate::curve::{NonRationalCurve, ParametricCurve};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector3;
+    // cubic polynomial lies in every cubic spline space, fitting reproduces it
+    let points = (0..=40)
+        .map(|i| {
+            let t = i as f64 / 40.0;
+            Vector3::new(t, t * t * t - t, 2.0 * t * t)
+        })
+        .collect::<Vec<_>>();
+    let params = Parameterization::Uniform.params(&points);
+    for n_control_points in 4..=10 {
+        let curve = BsplineCurve::fit_least_squares(&points, 3, n_control_points, &params).unwrap();
+        assert_eq!(curve.control_points().len(), n_control_points);
+        assert_eq!(curve.control_points()[0], points[0]);
+        assert_eq!(curve.control_points()[n_control_points - 1], points[40]);
+        for (u, q) in params.iter().zip(&points) {
+            let p = curve.get_point(*u);
+            assert_approx_eq!(p.x, q.x);
+            assert_approx_eq!(p.y, q.y);
+            assert_approx_eq!(p.z, q.z);
+        }
+    }
+    assert!(BsplineCurve::fit_least_squares(&points, 3, 3, &params).is_err());
+    assert!(BsplineCurve::fit_least_squares(&points, 3, 42, &params).is_err());
+    let fit = |params: &[f64]| BsplineCurve::fit_least_squares(&points, 3, 6, params);
+    assert!(matches!(
+        fit(&params[1..]),
+        Err(BasisFunctionError::LengthMismatch)
+    ));
+    assert!(matches!(
+        fit(&[0.5; 41]),
+        Err(BasisFunctionError::EmptyDomain)
+    ));
+    let mut bad = params.clone();
+    bad.swap(10, 11);
+    assert!(matches!(
+        fit(&bad),
+        Err(BasisFunctionError::NonMonotonicParams)
+    ));
+    bad[10] = f64::NAN;
+    assert!(matches!(fit(&bad), Err(BasisFunctionError::UIsNon)));
+    assert!(matches!(
+        BsplineCurve::fit_weighted_least_squares(&points, &[1.0; 40], 3, 6, &params),
+        Err(BasisFunctionError::LengthMismatch)
+    ));
+}
+
+#[test]
+fn test_bspline_curve_fit_weighted_least_squares() {
+    use crate::basics::Parameterization;
+    use crate::curve::NonRationalCurve;
+    use nalgebra::Vector2;
+    // noisy samples of a sine wave
+    let points = (0..=30)
+        .map(|i| {
+            let t = i as f64 / 30.0;
+            let noise = if i % 2 == 0 { 0.05 } else { -0.05 };
+            Vector2::new(t, (6.0 * t).sin() + noise)
+        })
+        .collect::<Vec<_>>();
+    let params = Parameterization::ChordLength.params(&points);
+    let plain = BsplineCurve::fit_least_squares(&points, 3, 8, &params).unwrap();
+    let mut weights = vec![1.0; points.len()];
+    weights[15] = 1000.0;
+    let weighted =
+        BsplineCurve::fit_weighted_least_squares(&points, &weights, 3, 8, &params).unwrap();
+    let error = |curve: &BsplineCurve<2>| (curve.get_point(params[15]) - points[15]).norm();
+    assert!(error(&weighted) < error(&plain));
+    assert!(error(&weighted) < 1e-2);
+    // unit weights give the same curve
+    let unit =
+        BsplineCurve::fit_weighted_least_squares(&points, &vec![1.0; 31], 3, 8, &params).unwrap();
+    for i in 0..=10 {
+        let u = i as f64 / 10.0;
+        assert!((unit.get_point(u) - plain.get_point(u)).norm() < 1e-12);
+    }
+}
+
+#[test]
+fn test_bspline_curve_approximate() {
+    use crate::basis_function::BasisFunctionError;
+    use crate::basics::Parameterization;
+    use crate::curve::{NonRationalCurve, ParametricCurve};
+    use nalgebra::Vector2;
+    // wave with a sharp bump in the middle
+    let points = (0..=200)
+        .map(|i| {
+            let t = i as f64 / 200.0;
+            Vector2::new(t, (4.0 * t).sin() + (-400.0 * (t - 0.5) * (t - 0.5)).exp())
+        })
+        .collect::<Vec<_>>();
+    let params = Parameterization::ChordLength.params(&points);
+    for eps in [1e-1, 1e-2, 1e-3, 1e-4] {
+        let (curve, deviation) = BsplineCurve::approximate(&points, 3, &params, eps).unwrap();
+        assert!(deviation <= eps);
+        assert!(curve.control_points().len() < points.len());
+        for (u, q) in params.iter().zip(&points) {
+            assert!((curve.get_point(*u) - q).norm() <= eps);
+        }
+    }
+    assert!(matches!(
+        BsplineCurve::approximate(&points, 3, &params, 0.0),
+        Err(BasisFunctionError::ToleranceNotReached(d)) if d > 0.0
+    ));
+    assert!(matches!(
+        BsplineCurve::approximate(&points, 3, &params[1..], 1e-3),
+        Err(BasisFunctionError::LengthMismatch)
+    ));
+    // points already on a cubic need no knot
+    let cubic = (0..=20)
+        .map(|i| {
+            let t = i as f64 / 20.0;
+            Vector2::new(t, t * t * t)
+        })
+        .collect::<Vec<_>>();
+    let params = Parameterization::Uniform.params(&cubic);
+    let (curve, _) = BsplineCurve::approximate(&cubic, 3, &params, 1e-9).unwrap();
+    assert_eq!(curve.control_points().len(), 4);
+}
+
+#[test]
+fn test_bspline_curve_closest_point() {
+    use crate::curve::NonRationalCurve;
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    let curve = BsplineCurve::new_uniform(
+        vec![
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 2.0),
+            Vector2::new(3.0, 2.0),
+            Vector2::new(4.0, -1.0),
+            Vector2::new(6.0, 0.0),
+            Vector2::new(7.0, 3.0),
+        ],
+        3,
+    );
+    // points offset from curve along normal project back to where they come from
+    for i in 1..10 {
+        let u = i as f64 / 10.0;
+        let ders = curve.get_ders(1, u);
+        let normal = Vector2::new(-ders[1].y, ders[1].x).normalize();
+        for offset in [0.0, 0.05, -0.05] {
+            let (t, point, distance) = curve.closest_point(&(ders[0] + normal * offset));
+            assert_approx_eq!(t, u, 1e-9);
+            assert_approx_eq!(point.x, ders[0].x, 1e-9);
+            assert_approx_eq!(point.y, ders[0].y, 1e-9);
+            assert_approx_eq!(distance, offset.abs(), 1e-9);
+        }
+    }
+    // points beyond the ends are clamped to the ends
+    let (u, point, distance) = curve.closest_point(&Vector2::new(-1.0, -1.0));
+    assert_eq!(u, 0.0);
+    assert_eq!(point, Vector2::new(0.0, 0.0));
+    assert_approx_eq!(distance, 2.0f64.sqrt());
+    let (u, _, _) = curve.closest_point(&Vector2::new(8.0, 5.0));
+    assert_eq!(u, 1.0);
+    // degree 0 curve is constant on every span
+    let constant = BsplineCurve::new_uniform(vec![Vector2::new(1.0, 2.0)], 0);
+    let (_, point, distance) = constant.closest_point(&Vector2::new(4.0, 6.0));
+    assert_eq!(point, Vector2::new(1.0, 2.0));
+    assert_approx_eq!(distance, 5.0);
+    let steps = BsplineCurve::new_uniform(
+        vec![
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 0.0),
+            Vector2::new(2.0, 0.0),
+        ],
+        0,
+    );
+    let (u, point, _) = steps.closest_point(&Vector2::new(1.1, 1.0));
+    assert_eq!(point, Vector2::new(1.0, 0.0));
+    assert!((1.0 / 3.0..2.0 / 3.0).contains(&u));
+}
+
+#[test]
+fn test_bspline_curve_reparametrize_reverse() {
+    use crate::curve::{NonRationalCurve, ParametricCurve};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    let bspline = BsplineCurve::new(
+        vec![
+            Vector2::new(200.0, 200.0),
+            Vector2::new(300.0, 300.0),
+            Vector2::new(500.0, 400.0),
+            Vector2::new(600.0, 300.0),
+            Vector2::new(700.0, 100.0),
+        ],
+        vec![0.0, 0.0, 0.0, 0.2, 0.7, 1.0, 1.0, 1.0],
+        2,
+    );
+    let moved = bspline.reparametrize(2.5, 17.0).unwrap();
+    assert_eq!(moved.domain(), (2.5, 17.0));
+    let reversed = moved.reverse();
+    assert_eq!(reversed.domain(), (2.5, 17.0));
+    for i in 0..=20 {
+        let t = i as f64 / 20.0;
+        let u = 2.5 + 14.5 * t;
+        for (a, b) in [
+            (bspline.get_point(t), moved.get_point(u)),
+            (moved.get_point(u), reversed.get_point(19.5 - u)),
+        ] {
+            assert_approx_eq!(a.x, b.x);
+            assert_approx_eq!(a.y, b.y);
+        }
+        let d = bspline.get_ders(1, t)[1] / 14.5;
+        let moved_d = moved.get_ders(1, u)[1];
+        let reversed_d = reversed.get_ders(1, 19.5 - u)[1];
+        assert_approx_eq!(d.x, moved_d.x);
+        assert_approx_eq!(d.x, -reversed_d.x);
+    }
+    assert!(bspline.reparametrize(1.0, 0.0).is_err());
+}
+
+#[test]
+fn test_bspline_curve_periodic() {
+    use crate::basis_function::{Basis, BasisFunctionError, Knots};
+    use crate::curve::{NonRationalCurve, ParametricCurve};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    let control_points = vec![
+        Vector2::new(0.0, 0.0),
+        Vector2::new(2.0, -1.0),
+        Vector2::new(4.0, 0.0),
+        Vector2::new(5.0, 2.0),
+        Vector2::new(3.0, 4.0),
+        Vector2::new(0.0, 3.0),
+    ];
+    let periodic = BsplineCurve::new_periodic(control_points.clone(), 3).unwrap();
+    assert_eq!(periodic.control_points().len(), 9);
+    assert_eq!(periodic.domain(), (0.0, 1.0));
+    assert!(!periodic.basis_function().knots().is_clamped(3));
+    // closed with C^2 continuity at the seam
+    let (start, end) = (periodic.get_ders(2, 0.0), periodic.get_ders(2, 1.0));
+    for (a, b) in start.iter().zip(&end) {
+        assert_approx_eq!(a.x, b.x);
+        assert_approx_eq!(a.y, b.y);
+    }
+
+    let clamped = periodic.clamp();
+    assert!(clamped.basis_function().knots().is_clamped(3));
+    assert_eq!(clamped.domain(), (0.0, 1.0));
+    let start = clamped.get_point(0.0);
+    assert_approx_eq!(start.x, clamped.control_points()[0].x);
+    assert_approx_eq!(start.y, clamped.control_points()[0].y);
+    for i in 0..=20 {
+        let u = i as f64 / 20.0;
+        let (a, b) = (periodic.get_point(u), clamped.get_point(u));
+        assert_approx_eq!(a.x, b.x);
+        assert_approx_eq!(a.y, b.y);
+    }
+
+    let unclamped = clamped.unclamp().unwrap();
+    for (a, b) in unclamped
+        .basis_function()
+        .knots()
+        .as_slice()
+        .iter()
+        .zip(periodic.basis_function().knots().as_slice())
+    {
+        assert_approx_eq!(a, b);
+    }
+    for (a, b) in unclamped
+        .control_points()
+        .iter()
+        .zip(periodic.control_points())
+    {
+        assert_approx_eq!(a.x, b.x);
+        assert_approx_eq!(a.y, b.y);
+    }
+    assert!(matches!(
+        periodic.unclamp(),
+        Err(BasisFunctionError::NotClamped)
+    ));
+    assert!(BsplineCurve::new_periodic(control_points[..3].to_vec(), 3).is_err());
+
+    // unclamped curves are clamped before degree elevation, decomposition and reduction
+    let uniform = BsplineCurve::try_new(
+        control_points.clone(),
+        Knots::uniform(2, 6).unwrap().as_slice().to_vec(),
+        2,
+    )
+    .unwrap();
+    for curve in [periodic, uniform] {
+        let (a, b) = curve.domain();
+        let elevated = curve.elevate_degree(1);
+        assert_eq!(elevated.degree(), curve.degree() + 1);
+        assert_eq!(elevated.domain(), (a, b));
+        let reduced = elevated.unclamp().unwrap().reduce_degree(1e-6).unwrap();
+        assert_eq!(reduced.degree(), curve.degree());
+        let segments = curve.decompose();
+        assert_eq!(
+            segments.len(),
+            curve.control_points().len() - curve.degree()
+        );
+        for i in 0..=20 {
+            let u = a + (b - a) * i as f64 / 20.0;
+            let expected = curve.get_point(u);
+            let segment = segments
+                .iter()
+                .find(|segment| u <= segment.domain().1)
+                .unwrap();
+            for point in [
+                elevated.get_point(u),
+                reduced.get_point(u),
+                segment.get_point(u),
+            ] {
+                assert_approx_eq!(expected.x, point.x);
+                assert_approx_eq!(expected.y, point.y);
+            }
+        }
+    }
+}
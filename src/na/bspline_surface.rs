@@ -24,4 +24,539 @@ fn test_bspline_surface_get_point() {}
 
 
 #[test]
-fn test_bspline_surface_get_ders() {}
\ No newline at end of file
+fn test_bspline_surface_get_ders() {}
+
+#[test]
+fn test_bspline_surface_refine_knots() {
+    use crate::basis_function::Basis;
+    use crate::surface::{NonRationalSurface, ParametricSurface};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector3;
+    let surface = BsplineSurface::new_uniform(
+        (0..5)
+            .map(|i| {
+                (0..4)
+                    .map(|j| Vector3::new(i as f64, j as f64, ((i * j) % 3) as f64))
+                    .collect()
+            })
+            .collect(),
+        3,
+        2,
+    );
+    let u_refined = surface.refine_knots_u(&[0.2, 0.5, 0.5]).unwrap();
+    assert_eq!(u_refined.control_points().len(), 8);
+    assert_eq!(u_refined.control_points()[0].len(), 4);
+    assert_eq!(u_refined.u_basis_function().knots().m(), 11);
+    let v_refined = surface.refine_knots_v(&[0.3, 0.6]).unwrap();
+    assert_eq!(v_refined.control_points().len(), 5);
+    assert_eq!(v_refined.control_points()[0].len(), 6);
+    assert_eq!(v_refined.v_basis_function().knots().m(), 8);
+    for i in 0..=10 {
+        for j in 0..=10 {
+            let (u, v) = (i as f64 / 10.0, j as f64 / 10.0);
+            let a = NonRationalSurface::get_point(&surface, u, v);
+            for refined in [&u_refined, &v_refined] {
+                let b = NonRationalSurface::get_point(refined, u, v);
+                assert_approx_eq!(a.x, b.x);
+                assert_approx_eq!(a.y, b.y);
+                assert_approx_eq!(a.z, b.z);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_bspline_surface_elevate_degree() {
+    use crate::basis_function::Knots;
+    use crate::surface::{NonRationalSurface, ParametricSurface};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector3;
+    let surface = BsplineSurface::new_uniform(
+        (0..5)
+            .map(|i| {
+                (0..4)
+                    .map(|j| Vector3::new(i as f64, j as f64, ((i * j) % 3) as f64))
+                    .collect()
+            })
+            .collect(),
+        2,
+        1,
+    );
+    // unclamped knots are clamped before degree elevation
+    let unclamped = BsplineSurface::try_new(
+        surface.control_points().clone(),
+        2,
+        Knots::uniform(2, 5).unwrap().as_slice().to_vec(),
+        1,
+        Knots::uniform(1, 4).unwrap().as_slice().to_vec(),
+    )
+    .unwrap();
+    for surface in [surface, unclamped] {
+        let elevated = surface.elevate_degree_u(1).elevate_degree_v(2);
+        assert_eq!(elevated.p(), 3);
+        assert_eq!(elevated.q(), 3);
+        assert_eq!(elevated.domain(), surface.domain());
+        for i in 0..=10 {
+            for j in 0..=10 {
+                let (u, v) = (i as f64 / 10.0, j as f64 / 10.0);
+                let a = NonRationalSurface::get_point(&surface, u, v);
+                let b = NonRationalSurface::get_point(&elevated, u, v);
+                assert_approx_eq!(a.x, b.x);
+                assert_approx_eq!(a.y, b.y);
+                assert_approx_eq!(a.z, b.z);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_bspline_surface_decompose() {
+    use crate::basis_function::Knots;
+    use crate::surface::{NonRationalSurface, ParametricSurface};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector3;
+    let surface = BsplineSurface::new_uniform(
+        (0..5)
+            .map(|i| {
+                (0..4)
+                    .map(|j| Vector3::new(i as f64, j as f64, ((i * j) % 3) as f64))
+                    .collect()
+            })
+            .collect(),
+        2,
+        2,
+    );
+    // unclamped knots on the same domain with the same breakpoints are clamped first
+    let unclamped = BsplineSurface::try_new(
+        surface.control_points().clone(),
+        2,
+        Knots::uniform(2, 5).unwrap().as_slice().to_vec(),
+        2,
+        Knots::uniform(2, 4).unwrap().as_slice().to_vec(),
+    )
+    .unwrap();
+    // u knots: 0, 1/3, 2/3, 1  v knots: 0, 1/2, 1
+    for surface in [surface, unclamped] {
+        let patches = surface.decompose();
+        assert_eq!(patches.len(), 3);
+        for (k, strip) in patches.iter().enumerate() {
+            assert_eq!(strip.len(), 2);
+            for (l, patch) in strip.iter().enumerate() {
+                assert_eq!(patch.p(), 2);
+                assert_eq!(patch.q(), 2);
+                let ((ua, ub), (va, vb)) = patch.domain();
+                assert_approx_eq!(ua, k as f64 / 3.0);
+                assert_approx_eq!(ub, (k + 1) as f64 / 3.0);
+                assert_approx_eq!(va, l as f64 / 2.0);
+                assert_approx_eq!(vb, (l + 1) as f64 / 2.0);
+                for i in 0..=5 {
+                    for j in 0..=5 {
+                        let (s, t) = (i as f64 / 5.0, j as f64 / 5.0);
+                        let (u, v) = (ua + s * (ub - ua), va + t * (vb - va));
+                        let a = NonRationalSurface::get_point(&surface, u, v);
+                        let b = patch.get_point(u, v);
+                        assert_approx_eq!(a.x, b.x);
+                        assert_approx_eq!(a.y, b.y);
+                        assert_approx_eq!(a.z, b.z);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_bspline_surface_split() {
+    use crate::basis_function::Basis;
+    use crate::surface::{NonRationalSurface, ParametricSurface};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector3;
+    let surface = BsplineSurface::new_uniform(
+        (0..5)
+            .map(|i| {
+                (0..4)
+                    .map(|j| Vector3::new(i as f64, j as f64, ((i * j) % 3) as f64))
+                    .collect()
+            })
+            .collect(),
+        3,
+        2,
+    );
+    let (bottom, top) = surface.split_u(0.4).unwrap();
+    let (left, right) = surface.split_v(0.5).unwrap();
+    let sub = surface.sub_surface(0.2..0.9, 0.1..0.6).unwrap();
+    assert_eq!(sub.u_basis_function().knots()[0], 0.2);
+    assert_eq!(sub.v_basis_function().knots()[0], 0.1);
+    for i in 0..=10 {
+        for j in 0..=10 {
+            let (u, v) = (i as f64 / 10.0, j as f64 / 10.0);
+            let a = NonRationalSurface::get_point(&surface, u, v);
+            let mut parts = vec![
+                if u <= 0.4 { &bottom } else { &top },
+                if v <= 0.5 { &left } else { &right },
+            ];
+            if (0.2..=0.9).contains(&u) && (0.1..=0.6).contains(&v) {
+                parts.push(&sub);
+            }
+            for part in parts {
+                let b = NonRationalSurface::get_point(part, u, v);
+                assert_approx_eq!(a.x, b.x);
+                assert_approx_eq!(a.y, b.y);
+                assert_approx_eq!(a.z, b.z);
+            }
+        }
+    }
+    assert!(surface.split_u(0.0).is_err());
+    assert!(surface.sub_surface(0.2..0.9, 0.1..1.1).is_err());
+}
+
+#[test]
+fn test_bspline_surface_iso_curve() {
+    use crate::basis_function::BasisFunctionError;
+    use crate::curve::{NonRationalCurve, ParametricCurve};
+    use crate::surface::{NonRationalSurface, ParametricSurface};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector3;
+    let surface = BsplineSurface::new_uniform(
+        (0..5)
+            .map(|i| {
+                (0..4)
+                    .map(|j| Vector3::new(i as f64, j as f64, ((i * j) % 3) as f64))
+                    .collect()
+            })
+            .collect(),
+        3,
+        2,
+    );
+    for i in 0..=10 {
+        let c = i as f64 / 10.0;
+        let u_curve = surface.iso_curve_u(c).unwrap();
+        let v_curve = surface.iso_curve_v(c).unwrap();
+        assert_eq!(u_curve.degree(), 2);
+        assert_eq!(u_curve.control_points().len(), 4);
+        assert_eq!(v_curve.degree(), 3);
+        assert_eq!(v_curve.control_points().len(), 5);
+        for j in 0..=10 {
+            let t = j as f64 / 10.0;
+            for (a, b) in [
+                (
+                    NonRationalSurface::get_point(&surface, c, t),
+                    u_curve.get_point(t),
+                ),
+                (
+                    NonRationalSurface::get_point(&surface, t, c),
+                    v_curve.get_point(t),
+                ),
+            ] {
+                assert_approx_eq!(a.x, b.x);
+                assert_approx_eq!(a.y, b.y);
+                assert_approx_eq!(a.z, b.z);
+            }
+        }
+    }
+    assert!(matches!(
+        surface.iso_curve_u(1.5),
+        Err(BasisFunctionError::UGreaterThanMax)
+    ));
+    assert!(matches!(
+        surface.iso_curve_u(f64::NAN),
+        Err(BasisFunctionError::UIsNon)
+    ));
+    assert!(surface.iso_curve_v(2.0).is_err());
+}
+
+#[test]
+fn test_bspline_surface_interpolate() {
+    use crate::basics::Parameterization;
+    use crate::basis_function::BasisFunctionError;
+    use crate::surface::{NonRationalSurface, ParametricSurface};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector3;
+    let points = (0..6)
+        .map(|i| {
+            (0..5)
+                .map(|j| {
+                    let x = i as f64 * 1.2 + 0.1 * j as f64;
+                    let y = j as f64 + 0.05 * (i * i) as f64;
+                    Vector3::new(x, y, (x * 0.7).sin() * (y * 0.5).cos())
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    for parameterization in [
+        Parameterization::Uniform,
+        Parameterization::ChordLength,
+        Parameterization::Centripetal,
+    ] {
+        let (u_params, v_params) = parameterization.grid_params(&points);
+        for (p, q) in [(1, 1), (2, 3), (3, 2), (5, 4)] {
+            let surface = BsplineSurface::interpolate(&points, p, q, parameterization).unwrap();
+            assert_eq!(surface.control_points().len(), 6);
+            assert_eq!(surface.control_points()[0].len(), 5);
+            for (k, u) in u_params.iter().enumerate() {
+                for (l, v) in v_params.iter().enumerate() {
+                    let s = NonRationalSurface::get_point(&surface, *u, *v);
+                    assert_approx_eq!(s.x, points[k][l].x);
+                    assert_approx_eq!(s.y, points[k][l].y);
+                    assert_approx_eq!(s.z, points[k][l].z);
+                }
+            }
+        }
+    }
+    assert!(BsplineSurface::interpolate(&points, 6, 2, Parameterization::Uniform).is_err());
+    assert!(BsplineSurface::interpolate(&points, 2, 0, Parameterization::Uniform).is_err());
+    let mut ragged = points.clone();
+    ragged[3].pop();
+    assert!(matches!(
+        BsplineSurface::interpolate(&ragged, 2, 2, Parameterization::Uniform),
+        Err(BasisFunctionError::RaggedControlNet)
+    ));
+}
+
+#[test]
+fn test_bspline_surface_fit_least_squares() {
+    use crate::basics::Parameterization;
+    use crate::basis_function::BasisFunctionError;
+    use crate::surface::ParametricSurface;
+    use nalgebra::Vector3;
+    let height_map = |f: fn(f64, f64) -> f64| {
+        (0..=40)
+            .map(|i| {
+                (0..=30)
+                    .map(|j| {
+                        let (x, y) = (i as f64 / 40.0, j as f64 / 30.0);
+                        Vector3::new(x, y, f(x, y))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+    };
+    // bicubic polynomial is reproduced
+    let points = height_map(|x, y| x * x * x - x * y * y + 0.5 * y);
+    let (surface, deviation) =
+        BsplineSurface::fit_least_squares(&points, 3, 3, 6, 5, Parameterization::Uniform).unwrap();
+    assert_eq!(surface.control_points().len(), 6);
+    assert_eq!(surface.control_points()[0].len(), 5);
+    assert!(deviation.max < 1e-12);
+
+    let points = height_map(|x, y| (5.0 * x).sin() * (4.0 * y).cos());
+    let mut last = f64::INFINITY;
+    for n in [5, 8, 12] {
+        let (surface, deviation) =
+            BsplineSurface::fit_least_squares(&points, 3, 3, n, n, Parameterization::Uniform)
+                .unwrap();
+        assert_eq!(surface.control_points()[0][0], points[0][0]);
+        assert_eq!(surface.control_points()[n - 1][n - 1], points[40][30]);
+        assert!(deviation.rms <= deviation.max);
+        assert!(deviation.max < last);
+        last = deviation.max;
+    }
+    assert!(last < 1e-3);
+    assert!(
+        BsplineSurface::fit_least_squares(&points, 3, 3, 42, 5, Parameterization::Uniform).is_err()
+    );
+    let mut ragged = points.clone();
+    ragged[7].push(Vector3::zeros());
+    assert!(matches!(
+        BsplineSurface::fit_least_squares(&ragged, 3, 3, 6, 5, Parameterization::Uniform),
+        Err(BasisFunctionError::RaggedControlNet)
+    ));
+}
+
+#[test]
+fn test_bspline_surface_fit_scattered() {
+    use crate::basis_function::BasisFunctionError;
+    use crate::surface::ParametricSurface;
+    use nalgebra::Vector3;
+    // quasi random params by golden ratio sequence
+    let params = (0..300)
+        .map(|k| k as f64)
+        .map(|k| ((k * 0.618_033_988_7).fract(), (k * 0.754_877_666_2).fract()))
+        .collect::<Vec<_>>();
+    let sample = |f: fn(f64, f64) -> f64, params: &[(f64, f64)]| {
+        params
+            .iter()
+            .map(|&(u, v)| Vector3::new(u, v, f(u, v)))
+            .collect::<Vec<_>>()
+    };
+    // bicubic polynomial is reproduced without smoothing
+    let points = sample(|x, y| x * x * y - y * y * y + 0.3 * x, &params);
+    let (surface, deviation) =
+        BsplineSurface::fit_scattered(&points, &params, 3, 3, 6, 7, 0.0).unwrap();
+    assert_eq!(surface.control_points().len(), 6);
+    assert_eq!(surface.control_points()[0].len(), 7);
+    assert!(deviation.max < 1e-9);
+
+    // plane has no bending energy and is reproduced with smoothing
+    let points = sample(|x, y| 2.0 * x - y + 1.0, &params);
+    let (_, deviation) = BsplineSurface::fit_scattered(&points, &params, 3, 2, 8, 8, 1.0).unwrap();
+    assert!(deviation.max < 1e-9);
+
+    // points only near the diagonal leave most control points undetermined
+    let sparse = params
+        .iter()
+        .filter(|(u, v)| (u - v).abs() < 0.05)
+        .copied()
+        .collect::<Vec<_>>();
+    let points = sample(|x, y| (3.0 * x).sin() + y, &sparse);
+    assert!(matches!(
+        BsplineSurface::fit_scattered(&points, &sparse, 3, 3, 10, 10, 0.0),
+        Err(BasisFunctionError::SingularMatrix)
+    ));
+    let (_, deviation) =
+        BsplineSurface::fit_scattered(&points, &sparse, 3, 3, 10, 10, 1e-6).unwrap();
+    assert!(deviation.rms <= deviation.max);
+    assert!(deviation.max < 1e-2);
+    assert!(matches!(
+        BsplineSurface::fit_scattered(&points, &params, 3, 3, 6, 7, 0.0),
+        Err(BasisFunctionError::LengthMismatch)
+    ));
+}
+
+#[test]
+fn test_bspline_surface_closest_point() {
+    use crate::surface::NonRationalSurface;
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector3;
+    for (p, q) in [(3, 3), (1, 3), (2, 1)] {
+        let surface = BsplineSurface::new_uniform(
+            (0..5)
+                .map(|i| {
+                    (0..6)
+                        .map(|j| {
+                            let z = 0.3 * ((i * j) % 3) as f64 - 0.2 * (i % 2) as f64;
+                            Vector3::new(i as f64, j as f64, z)
+                        })
+                        .collect()
+                })
+                .collect(),
+            p,
+            q,
+        );
+        // points offset from surface along normal project back to where they come from
+        for (u, v) in [(0.3, 0.4), (0.55, 0.15), (0.8, 0.75), (0.1, 0.9)] {
+            let ders = surface.get_ders(1, u, v);
+            let normal = ders[1][0].cross(&ders[0][1]).normalize();
+            for offset in [0.0, 0.02, -0.02] {
+                let (s, t, point, distance) = surface
+                    .closest_point(&(ders[0][0] + normal * offset))
+                    .unwrap();
+                assert_approx_eq!(s, u, 1e-8);
+                assert_approx_eq!(t, v, 1e-8);
+                assert_approx_eq!(point.x, ders[0][0].x, 1e-8);
+                assert_approx_eq!(point.y, ders[0][0].y, 1e-8);
+                assert_approx_eq!(point.z, ders[0][0].z, 1e-8);
+                assert_approx_eq!(distance, offset.abs(), 1e-8);
+            }
+        }
+    }
+    // points beyond boundary are clamped to boundary
+    let plane = BsplineSurface::new_uniform(
+        (0..3)
+            .map(|i| {
+                (0..3)
+                    .map(|j| Vector3::new(i as f64, j as f64, 0.0))
+                    .collect()
+            })
+            .collect(),
+        2,
+        2,
+    );
+    let (u, v, point, distance) = plane.closest_point(&Vector3::new(3.0, 1.0, 1.0)).unwrap();
+    assert_eq!(u, 1.0);
+    assert_approx_eq!(v, 0.5);
+    assert_approx_eq!(point.y, 1.0);
+    assert_approx_eq!(distance, 2.0f64.sqrt());
+    let (u, v, _, distance) = plane.closest_point(&Vector3::new(-1.0, -1.0, 0.0)).unwrap();
+    assert_eq!((u, v), (0.0, 0.0));
+    assert_approx_eq!(distance, 2.0f64.sqrt());
+    // targets far outside a curved surface near a corner and near an edge
+    let surface = BsplineSurface::new_uniform(
+        (0..6)
+            .map(|i| {
+                (0..5)
+                    .map(|j| Vector3::new(i as f64, j as f64, 0.5 * ((i + 2 * j) % 3) as f64))
+                    .collect()
+            })
+            .collect(),
+        3,
+        3,
+    );
+    for (target, corner_u, corner_v) in [
+        (Vector3::new(-5.0, -5.0, 0.0), Some(0.0), Some(0.0)),
+        (Vector3::new(10.0, 2.0, -1.0), Some(1.0), None),
+        (Vector3::new(2.5, 9.0, 3.0), None, Some(1.0)),
+    ] {
+        let (u, v, point, distance) = surface.closest_point(&target).unwrap();
+        assert_approx_eq!(distance, (point - target).norm());
+        if let Some(corner_u) = corner_u {
+            assert_eq!(u, corner_u);
+        }
+        if let Some(corner_v) = corner_v {
+            assert_eq!(v, corner_v);
+        }
+        // no point of a dense grid is closer
+        for i in 0..=100 {
+            for j in 0..=100 {
+                let (s, t) = (i as f64 / 100.0, j as f64 / 100.0);
+                let grid_point = NonRationalSurface::get_point(&surface, s, t);
+                assert!(distance <= (grid_point - target).norm() + 1e-9);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_bspline_surface_get_ders_shape() {
+    use crate::surface::NonRationalSurface;
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector3;
+    let surface = BsplineSurface::new_uniform(
+        (0..5)
+            .map(|i| {
+                (0..4)
+                    .map(|j| Vector3::new(i as f64, j as f64, ((i * j) % 3) as f64))
+                    .collect()
+            })
+            .collect(),
+        3,
+        2,
+    );
+    let (u, v) = (0.3, 0.6);
+    let first = surface.get_ders(1, u, v);
+    assert_eq!(first.len(), 2);
+    assert!(first.iter().all(|row| row.len() == 2));
+    // SKL[k][l] for every k + l <= 4, zeros above the total order or the degrees
+    let ders = surface.get_ders(4, u, v);
+    assert_eq!(ders.len(), 5);
+    assert!(ders.iter().all(|row| row.len() == 5));
+    for (k, row) in ders.iter().enumerate() {
+        for (l, der) in row.iter().enumerate() {
+            if k + l > 4 || k > 3 || l > 2 {
+                assert_eq!(*der, Vector3::zeros());
+            }
+        }
+    }
+    for (a, b) in [
+        (first[0][0], ders[0][0]),
+        (first[1][0], ders[1][0]),
+        (first[0][1], ders[0][1]),
+    ] {
+        assert_approx_eq!(a.x, b.x);
+        assert_approx_eq!(a.y, b.y);
+        assert_approx_eq!(a.z, b.z);
+    }
+    // SKL[1][0] and SKL[0][1] by central differences
+    let h = 1e-6;
+    let du = (NonRationalSurface::get_point(&surface, u + h, v)
+        - NonRationalSurface::get_point(&surface, u - h, v))
+        / (2.0 * h);
+    let dv = (NonRationalSurface::get_point(&surface, u, v + h)
+        - NonRationalSurface::get_point(&surface, u, v - h))
+        / (2.0 * h);
+    for (a, b) in [(du, ders[1][0]), (dv, ders[0][1])] {
+        assert_approx_eq!(a.x, b.x, 1e-6);
+        assert_approx_eq!(a.y, b.y, 1e-6);
+        assert_approx_eq!(a.z, b.z, 1e-6);
+    }
+}
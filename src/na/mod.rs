@@ -4,6 +4,8 @@ mod bspline_curve;
 mod bspline_surface;
 mod nurbs_curve;
 mod nurbs_surface;
+mod rational_bezier_curve;
+mod rational_bezier_surface;
 
 pub use nalgebra;
 pub use bezier_curve::BezierCurve;
@@ -12,3 +14,5 @@ pub use bspline_curve::BsplineCurve;
 pub use bspline_surface::BsplineSurface;
 pub use nurbs_curve::NurbsCurve;
 pub use nurbs_surface::NurbsSurface;
+pub use rational_bezier_curve::RationalBezierCurve;
+pub use rational_bezier_surface::RationalBezierSurface;
@@ -47,3 +47,471 @@ fn test_nurbs_get_ders() {
         ]
     );
 }
+
+#[test]
+fn test_nurbs_curve_insert_knot() {
+    use crate::curve::{ParametricCurve, RationalCurve};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    let curve = NurbsCurve::<2>::new(
+        vec![
+            (Vector2::new(200.0, 200.0), 0.1),
+            (Vector2::new(300.0, 300.0), 1.0),
+            (Vector2::new(500.0, 400.0), 2.0),
+            (Vector2::new(600.0, 300.0), 1.0),
+        ],
+        vec![0.0, 0.0, 0.0, 0.5, 1.0, 1.0, 1.0],
+        2,
+    );
+    for (u, times) in [(0.3, 1), (0.3, 2), (0.5, 1)] {
+        let refined = curve.insert_knot(u, times).unwrap();
+        assert_eq!(
+            refined.control_points().len(),
+            curve.control_points().len() + times
+        );
+        for i in 0..=20 {
+            let t = i as f64 / 20.0;
+            let a = curve.get_point(t);
+            let b = refined.get_point(t);
+            assert_approx_eq!(a.x, b.x);
+            assert_approx_eq!(a.y, b.y);
+        }
+    }
+    assert!(curve.insert_knot(0.5, 2).is_err());
+}
+
+#[test]
+fn test_nurbs_curve_refine_knots() {
+    use crate::curve::{ParametricCurve, RationalCurve};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    let curve = NurbsCurve::<2>::new(
+        vec![
+            (Vector2::new(200.0, 200.0), 0.1),
+            (Vector2::new(300.0, 300.0), 1.0),
+            (Vector2::new(500.0, 400.0), 2.0),
+            (Vector2::new(600.0, 300.0), 1.0),
+        ],
+        vec![0.0, 0.0, 0.0, 0.5, 1.0, 1.0, 1.0],
+        2,
+    );
+    let refined = curve.refine_knots(&[0.2, 0.5, 0.7, 0.7]).unwrap();
+    assert_eq!(refined.control_points().len(), 8);
+    for i in 0..=20 {
+        let t = i as f64 / 20.0;
+        let a = curve.get_point(t);
+        let b = refined.get_point(t);
+        assert_approx_eq!(a.x, b.x);
+        assert_approx_eq!(a.y, b.y);
+    }
+}
+
+#[test]
+fn test_nurbs_curve_remove_knot() {
+    use crate::curve::{ParametricCurve, RationalCurve};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    let curve = NurbsCurve::<2>::new(
+        vec![
+            (Vector2::new(200.0, 200.0), 0.1),
+            (Vector2::new(300.0, 300.0), 1.0),
+            (Vector2::new(500.0, 400.0), 2.0),
+            (Vector2::new(600.0, 300.0), 1.0),
+        ],
+        vec![0.0, 0.0, 0.0, 0.5, 1.0, 1.0, 1.0],
+        2,
+    );
+    let refined = curve.refine_knots(&[0.25, 0.5, 0.7]).unwrap();
+    let (removed, times, _) = refined.remove_knot(0.5, 2, 1e-6).unwrap();
+    assert_eq!(times, 1);
+    let (removed, times, _) = removed.remove_knot(0.25, 1, 1e-6).unwrap();
+    assert_eq!(times, 1);
+    let (removed, times, deviation) = removed.remove_knot(0.7, 1, 1e-6).unwrap();
+    assert_eq!(times, 1);
+    assert!(deviation < 1e-6);
+    assert_eq!(removed.control_points().len(), 4);
+    for i in 0..=20 {
+        let t = i as f64 / 20.0;
+        let a = curve.get_point(t);
+        let b = removed.get_point(t);
+        assert_approx_eq!(a.x, b.x);
+        assert_approx_eq!(a.y, b.y);
+    }
+    let (_, times, _) = curve.remove_knot(0.5, 1, 1e-6).unwrap();
+    assert_eq!(times, 0);
+}
+
+#[test]
+fn test_nurbs_curve_elevate_degree() {
+    use crate::curve::{ParametricCurve, RationalCurve};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    let curve = NurbsCurve::<2>::new(
+        vec![
+            (Vector2::new(1.0, 0.0), 1.0),
+            (Vector2::new(1.0, 1.0), 1.0),
+            (Vector2::new(0.0, 1.0), 2.0),
+        ],
+        vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
+        2,
+    );
+    let elevated = curve.elevate_degree(2);
+    assert_eq!(elevated.degree(), 4);
+    assert_eq!(elevated.control_points().len(), 5);
+    for i in 0..=10 {
+        let u = i as f64 / 10.0;
+        let a = curve.get_point(u);
+        let b = elevated.get_point(u);
+        assert_approx_eq!(a.x, b.x);
+        assert_approx_eq!(a.y, b.y);
+    }
+}
+
+#[test]
+fn test_nurbs_curve_reduce_degree() {
+    use crate::basis_function::BasisFunctionError;
+    use crate::curve::{ParametricCurve, RationalCurve};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    let curve = NurbsCurve::<2>::new(
+        vec![
+            (Vector2::new(200.0, 200.0), 0.1),
+            (Vector2::new(300.0, 300.0), 1.0),
+            (Vector2::new(500.0, 400.0), 2.0),
+            (Vector2::new(600.0, 300.0), 1.0),
+        ],
+        vec![0.0, 0.0, 0.0, 0.5, 1.0, 1.0, 1.0],
+        2,
+    );
+    let reduced = curve.elevate_degree(1).reduce_degree(1e-6).unwrap();
+    assert_eq!(reduced.degree(), 2);
+    for i in 0..=20 {
+        let t = i as f64 / 20.0;
+        let a = curve.get_point(t);
+        let b = reduced.get_point(t);
+        assert_approx_eq!(a.x, b.x);
+        assert_approx_eq!(a.y, b.y);
+    }
+    assert!(matches!(
+        curve.reduce_degree(1e-3),
+        Err(BasisFunctionError::NotDegreeReducible(_))
+    ));
+}
+
+#[test]
+fn test_nurbs_curve_decompose() {
+    use crate::curve::{ParametricCurve, RationalCurve};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    let curve = NurbsCurve::<2>::new(
+        vec![
+            (Vector2::new(200.0, 200.0), 0.1),
+            (Vector2::new(300.0, 300.0), 1.0),
+            (Vector2::new(500.0, 400.0), 2.0),
+            (Vector2::new(600.0, 300.0), 1.0),
+        ],
+        vec![0.0, 0.0, 0.0, 0.5, 1.0, 1.0, 1.0],
+        2,
+    );
+    let segments = curve.decompose();
+    assert_eq!(segments.len(), 2);
+    for (k, segment) in segments.iter().enumerate() {
+        assert_eq!(segment.domain(), (0.5 * k as f64, 0.5 * (k + 1) as f64));
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            let u = 0.5 * (k as f64 + t);
+            let a = curve.get_point(u);
+            let b = segment.get_point(u);
+            assert_approx_eq!(a.x, b.x);
+            assert_approx_eq!(a.y, b.y);
+        }
+    }
+}
+
+#[test]
+fn test_nurbs_curve_split() {
+    use crate::curve::RationalCurve;
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    let curve = NurbsCurve::<2>::new(
+        vec![
+            (Vector2::new(200.0, 200.0), 0.1),
+            (Vector2::new(300.0, 300.0), 1.0),
+            (Vector2::new(500.0, 400.0), 2.0),
+            (Vector2::new(600.0, 300.0), 1.0),
+        ],
+        vec![0.0, 0.0, 0.0, 0.5, 1.0, 1.0, 1.0],
+        2,
+    );
+    let (left, right) = curve.split_at(0.4).unwrap();
+    let sub = curve.sub_curve(0.2, 0.9).unwrap();
+    for i in 0..=10 {
+        let t = i as f64 / 10.0;
+        for (a, b) in [
+            (curve.get_point(0.4 * t), left.get_point(0.4 * t)),
+            (curve.get_point(0.4 + 0.6 * t), right.get_point(0.4 + 0.6 * t)),
+            (curve.get_point(0.2 + 0.7 * t), sub.get_point(0.2 + 0.7 * t)),
+        ] {
+            assert_approx_eq!(a.x, b.x);
+            assert_approx_eq!(a.y, b.y);
+        }
+    }
+}
+
+#[test]
+fn test_nurbs_curve_closest_point() {
+    use crate::curve::RationalCurve;
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    // quarter circle of radius 2
+    let w = 0.5f64.sqrt();
+    let curve = NurbsCurve::<2>::new(
+        vec![
+            (Vector2::new(2.0, 0.0), 1.0),
+            (Vector2::new(2.0, 2.0), w),
+            (Vector2::new(0.0, 2.0), 1.0),
+        ],
+        vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
+        2,
+    );
+    for i in 1..10 {
+        let angle = std::f64::consts::FRAC_PI_2 * i as f64 / 10.0;
+        for r in [0.5, 1.9, 2.0, 3.0] {
+            let p = Vector2::new(angle.cos(), angle.sin()) * r;
+            let (u, point, distance) = curve.closest_point(&p);
+            let expected = Vector2::new(angle.cos(), angle.sin()) * 2.0;
+            assert_approx_eq!(point.x, expected.x, 1e-9);
+            assert_approx_eq!(point.y, expected.y, 1e-9);
+            assert_approx_eq!(distance, (r - 2.0f64).abs(), 1e-9);
+            let on_curve = curve.get_point(u);
+            assert_approx_eq!(on_curve.x, point.x);
+            assert_approx_eq!(on_curve.y, point.y);
+        }
+    }
+}
+
+#[test]
+fn test_nurbs_curve_periodic() {
+    use crate::basis_function::{Basis, Knots};
+    use crate::curve::{ParametricCurve, RationalCurve};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    let periodic = NurbsCurve::new_periodic(
+        vec![
+            (Vector2::new(1.0, 0.0), 1.0),
+            (Vector2::new(1.0, 1.0), 2.0),
+            (Vector2::new(-1.0, 1.0), 0.5),
+            (Vector2::new(-1.0, -1.0), 1.0),
+            (Vector2::new(1.0, -1.0), 3.0),
+        ],
+        2,
+    )
+    .unwrap();
+    let (start, end) = (periodic.get_ders(1, 0.0), periodic.get_ders(1, 1.0));
+    for (a, b) in start.iter().zip(&end) {
+        assert_approx_eq!(a.x, b.x);
+        assert_approx_eq!(a.y, b.y);
+    }
+    let clamped = periodic.clamp();
+    assert!(clamped.basis_function().knots().is_clamped(2));
+    for i in 0..=20 {
+        let u = i as f64 / 20.0;
+        let (a, b) = (periodic.get_point(u), clamped.get_point(u));
+        assert_approx_eq!(a.x, b.x);
+        assert_approx_eq!(a.y, b.y);
+    }
+    let unclamped = clamped.unclamp().unwrap();
+    for (a, b) in unclamped
+        .control_points()
+        .iter()
+        .zip(periodic.control_points())
+    {
+        assert_approx_eq!(a.a().x, b.a().x);
+        assert_approx_eq!(a.a().y, b.a().y);
+        assert_approx_eq!(a.w(), b.w());
+    }
+    assert!(NurbsCurve::new_periodic(vec![(Vector2::new(1.0, 0.0), -1.0); 3], 2).is_err());
+
+    // unclamped curves are clamped before degree elevation, decomposition and reduction
+    let uniform = NurbsCurve::try_new(
+        vec![
+            (Vector2::new(1.0, 0.0), 1.0),
+            (Vector2::new(1.0, 1.0), 2.0),
+            (Vector2::new(-1.0, 1.0), 0.5),
+            (Vector2::new(-1.0, -1.0), 1.0),
+        ],
+        Knots::uniform(2, 4).unwrap().as_slice().to_vec(),
+        2,
+    )
+    .unwrap();
+    for curve in [periodic, uniform] {
+        let (a, b) = curve.domain();
+        let elevated = curve.elevate_degree(1);
+        assert_eq!(elevated.degree(), curve.degree() + 1);
+        assert_eq!(elevated.domain(), (a, b));
+        let reduced = elevated.unclamp().unwrap().reduce_degree(1e-6).unwrap();
+        assert_eq!(reduced.degree(), curve.degree());
+        let segments = curve.decompose();
+        assert_eq!(
+            segments.len(),
+            curve.control_points().len() - curve.degree()
+        );
+        for i in 0..=20 {
+            let u = a + (b - a) * i as f64 / 20.0;
+            let expected = curve.get_point(u);
+            let segment = segments
+                .iter()
+                .find(|segment| u <= segment.domain().1)
+                .unwrap();
+            for point in [
+                elevated.get_point(u),
+                reduced.get_point(u),
+                segment.get_point(u),
+            ] {
+                assert_approx_eq!(expected.x, point.x);
+                assert_approx_eq!(expected.y, point.y);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_nurbs_curve_circle_arc() {
+    use crate::curve::{ParametricCurve, RationalCurve};
+    use nalgebra::Vector3;
+    use std::f64::consts::{FRAC_PI_2, PI, TAU};
+    let center = Vector3::new(1.0, -2.0, 3.0);
+    let (x_axis, y_axis) = (Vector3::new(0.0, 0.6, 0.8), Vector3::new(0.0, -0.8, 0.6));
+    let radius = 2.5;
+    let on_circle = |angle: f64| center + (x_axis * angle.cos() + y_axis * angle.sin()) * radius;
+    let curves = [
+        NurbsCurve::circle(center, x_axis, y_axis, radius).unwrap(),
+        NurbsCurve::arc(center, x_axis, y_axis, radius, 0.3, 0.3 + FRAC_PI_2).unwrap(),
+        NurbsCurve::arc(center, x_axis, y_axis, radius, -PI, 2.0).unwrap(),
+        NurbsCurve::arc(center, x_axis, y_axis, radius, 1.0, 1.0 + TAU).unwrap(),
+        NurbsCurve::arc_through_points(on_circle(0.2), on_circle(2.5), on_circle(4.0)).unwrap(),
+        NurbsCurve::arc_through_points(on_circle(4.0), on_circle(2.5), on_circle(0.2)).unwrap(),
+        NurbsCurve::arc_through_points(on_circle(0.2), on_circle(5.0), on_circle(4.0)).unwrap(),
+    ];
+    for (curve, segments) in curves.iter().zip([4, 1, 4, 4, 3, 3, 2]) {
+        assert_eq!(curve.control_points().len(), 2 * segments + 1);
+        for i in 0..=100 {
+            let r = (curve.get_point(i as f64 / 100.0) - center).norm();
+            assert!((r - radius).abs() <= 8.0 * f64::EPSILON * radius);
+        }
+    }
+    let arc = &curves[1];
+    assert!((arc.get_point(0.0) - on_circle(0.3)).norm() < 1e-14);
+    assert!((arc.get_point(1.0) - on_circle(0.3 + FRAC_PI_2)).norm() < 1e-14);
+    for (curve, (a, b, c)) in
+        curves[4..]
+            .iter()
+            .zip([(0.2, 2.5, 4.0), (4.0, 2.5, 0.2), (0.2, 5.0, 4.0)])
+    {
+        assert_eq!(curve.get_point(0.0), on_circle(a));
+        assert_eq!(curve.get_point(1.0), on_circle(c));
+        let (_, _, distance) = curve.closest_point(&on_circle(b));
+        assert!(distance < 1e-9);
+    }
+
+    assert!(NurbsCurve::circle(center, x_axis, y_axis, 0.0).is_err());
+    assert!(NurbsCurve::arc(center, x_axis, y_axis, 1.0, 1.0, 1.0).is_err());
+    assert!(NurbsCurve::arc(center, x_axis, y_axis, 1.0, 0.0, 7.0).is_err());
+    assert!(NurbsCurve::arc_through_points(
+        Vector3::new(0.0, 0.0, 0.0),
+        Vector3::new(1.0, 1.0, 1.0),
+        Vector3::new(2.0, 2.0, 2.0),
+    )
+    .is_err());
+}
+
+#[test]
+fn test_nurbs_curve_ellipse_conic() {
+    use crate::curve::{ParametricCurve, RationalCurve};
+    use nalgebra::Vector2;
+    use std::f64::consts::FRAC_1_SQRT_2;
+    let (x_axis, y_axis) = (Vector2::new(1.0, 0.0), Vector2::new(0.0, 1.0));
+    let ellipse = NurbsCurve::ellipse(Vector2::zeros(), x_axis, y_axis, 3.0, 2.0).unwrap();
+    for i in 0..=100 {
+        let p = ellipse.get_point(i as f64 / 100.0);
+        assert!((p.x * p.x / 9.0 + p.y * p.y / 4.0 - 1.0).abs() < 1e-14);
+    }
+
+    // quarter circle, semicircle with parallel end tangents and 270 degree arc
+    let unit = |x: f64, y: f64| Vector2::new(x, y);
+    let arcs = [
+        (
+            unit(1.0, 0.0),
+            unit(0.0, 1.0),
+            unit(0.0, 1.0),
+            unit(-1.0, 0.0),
+            unit(FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+            1,
+        ),
+        (
+            unit(1.0, 0.0),
+            unit(0.0, 1.0),
+            unit(-1.0, 0.0),
+            unit(0.0, -1.0),
+            unit(0.0, 1.0),
+            2,
+        ),
+        (
+            unit(1.0, 0.0),
+            unit(0.0, 1.0),
+            unit(0.0, -1.0),
+            unit(1.0, 0.0),
+            unit(-1.0, 0.0),
+            2,
+        ),
+    ];
+    for (p0, t0, p2, t2, p, segments) in arcs {
+        let arc = NurbsCurve::conic_arc(p0, t0, p2, t2, p).unwrap();
+        assert_eq!(arc.control_points().len(), 2 * segments + 1);
+        assert!(arc.control_points().iter().all(|cp| cp.w() > 0.0));
+        for i in 0..=100 {
+            let r = arc.get_point(i as f64 / 100.0).norm();
+            assert!((r - 1.0).abs() <= 4.0 * f64::EPSILON);
+        }
+        let (_, _, distance) = arc.closest_point(&p);
+        assert!(distance < 1e-9);
+        let (start, end) = (arc.get_ders(1, 0.0), arc.get_ders(1, 1.0));
+        assert_eq!(start[0], p0);
+        assert_eq!(end[0], p2);
+        assert!(start[1].perp(&t0).abs() < 1e-12 && start[1].dot(&t0) > 0.0);
+        assert!(end[1].perp(&t2).abs() < 1e-12 && end[1].dot(&t2) > 0.0);
+    }
+
+    // hyperbola x y = 1 and parabola y = x^2
+    let hyperbola = NurbsCurve::conic_arc(
+        unit(0.5, 2.0),
+        unit(1.0, -4.0),
+        unit(2.0, 0.5),
+        unit(1.0, -0.25),
+        unit(1.0, 1.0),
+    )
+    .unwrap();
+    assert!(hyperbola.control_points()[1].w() > 1.0);
+    let parabola = NurbsCurve::conic_arc(
+        unit(-1.0, 1.0),
+        unit(1.0, -2.0),
+        unit(1.0, 1.0),
+        unit(1.0, 2.0),
+        unit(0.0, 0.0),
+    )
+    .unwrap();
+    for i in 0..=100 {
+        let u = i as f64 / 100.0;
+        let (h, p) = (hyperbola.get_point(u), parabola.get_point(u));
+        assert!((h.x * h.y - 1.0).abs() < 1e-14);
+        assert!((p.y - p.x * p.x).abs() < 1e-14);
+    }
+    assert!(NurbsCurve::conic_arc(
+        unit(1.0, 0.0),
+        unit(0.0, 1.0),
+        unit(0.0, 1.0),
+        unit(-1.0, 0.0),
+        unit(2.0, 2.0),
+    )
+    .is_err());
+}
@@ -32,3 +32,383 @@ fn test_nurbs_surface_get_point() {}
 
 #[test]
 fn test_nurbs_surface_get_ders() {}
+
+#[test]
+fn test_nurbs_surface_refine_knots() {
+    use crate::surface::{ParametricSurface, RationalSurface};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector3;
+    let surface = NurbsSurface::new_uniform(
+        (0..4)
+            .map(|i| {
+                (0..4)
+                    .map(|j| {
+                        (
+                            Vector3::new(i as f64, j as f64, ((i + j) % 2) as f64),
+                            1.0 + ((i * j) % 3) as f64,
+                        )
+                    })
+                    .collect()
+            })
+            .collect(),
+        2,
+        2,
+    );
+    let refined = surface
+        .refine_knots_u(&[0.25, 0.75])
+        .and_then(|s| s.refine_knots_v(&[0.1, 0.5]))
+        .unwrap();
+    assert_eq!(refined.control_points().len(), 6);
+    assert_eq!(refined.control_points()[0].len(), 6);
+    for i in 0..=10 {
+        for j in 0..=10 {
+            let (u, v) = (i as f64 / 10.0, j as f64 / 10.0);
+            let a = surface.get_point(u, v);
+            let b = refined.get_point(u, v);
+            assert_approx_eq!(a.x, b.x);
+            assert_approx_eq!(a.y, b.y);
+            assert_approx_eq!(a.z, b.z);
+        }
+    }
+}
+
+#[test]
+fn test_nurbs_surface_elevate_degree() {
+    use crate::basis_function::Knots;
+    use crate::surface::{ParametricSurface, RationalSurface};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector3;
+    let surface = NurbsSurface::new_uniform(
+        (0..4)
+            .map(|i| {
+                (0..3)
+                    .map(|j| {
+                        (
+                            Vector3::new(i as f64, j as f64, ((i + j) % 2) as f64),
+                            1.0 + ((i * j) % 3) as f64,
+                        )
+                    })
+                    .collect()
+            })
+            .collect(),
+        2,
+        2,
+    );
+    // unclamped knots are clamped before degree elevation
+    let unclamped = NurbsSurface::try_new(
+        surface.control_points().clone(),
+        2,
+        Knots::uniform(2, 4).unwrap().as_slice().to_vec(),
+        2,
+        Knots::uniform(2, 3).unwrap().as_slice().to_vec(),
+    )
+    .unwrap();
+    for surface in [surface, unclamped] {
+        let elevated = surface.elevate_degree_u(1).elevate_degree_v(1);
+        assert_eq!(elevated.p(), 3);
+        assert_eq!(elevated.q(), 3);
+        assert_eq!(elevated.domain(), surface.domain());
+        for i in 0..=10 {
+            for j in 0..=10 {
+                let (u, v) = (i as f64 / 10.0, j as f64 / 10.0);
+                let a = surface.get_point(u, v);
+                let b = elevated.get_point(u, v);
+                assert_approx_eq!(a.x, b.x);
+                assert_approx_eq!(a.y, b.y);
+                assert_approx_eq!(a.z, b.z);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_nurbs_surface_decompose() {
+    use crate::basis_function::Knots;
+    use crate::surface::{ParametricSurface, RationalSurface};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector3;
+    let surface = NurbsSurface::new_uniform(
+        (0..4)
+            .map(|i| {
+                (0..3)
+                    .map(|j| {
+                        (
+                            Vector3::new(i as f64, j as f64, ((i + j) % 2) as f64),
+                            1.0 + ((i * j) % 3) as f64,
+                        )
+                    })
+                    .collect()
+            })
+            .collect(),
+        2,
+        1,
+    );
+    // unclamped knots on the same domain with the same breakpoints are clamped first
+    let unclamped = NurbsSurface::try_new(
+        surface.control_points().clone(),
+        2,
+        Knots::uniform(2, 4).unwrap().as_slice().to_vec(),
+        1,
+        Knots::uniform(1, 3).unwrap().as_slice().to_vec(),
+    )
+    .unwrap();
+    // u knots: 0, 1/2, 1  v knots: 0, 1/2, 1
+    for surface in [surface, unclamped] {
+        let patches = surface.decompose();
+        assert_eq!(patches.len(), 2);
+        for (k, strip) in patches.iter().enumerate() {
+            assert_eq!(strip.len(), 2);
+            for (l, patch) in strip.iter().enumerate() {
+                assert_eq!(
+                    patch.domain(),
+                    (
+                        (k as f64 / 2.0, (k + 1) as f64 / 2.0),
+                        (l as f64 / 2.0, (l + 1) as f64 / 2.0)
+                    )
+                );
+                for i in 0..=5 {
+                    for j in 0..=5 {
+                        let (s, t) = (i as f64 / 5.0, j as f64 / 5.0);
+                        let (u, v) = ((k as f64 + s) / 2.0, (l as f64 + t) / 2.0);
+                        let a = surface.get_point(u, v);
+                        let b = patch.get_point(u, v);
+                        assert_approx_eq!(a.x, b.x);
+                        assert_approx_eq!(a.y, b.y);
+                        assert_approx_eq!(a.z, b.z);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_nurbs_surface_split() {
+    use crate::surface::RationalSurface;
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector3;
+    let surface = NurbsSurface::new_uniform(
+        (0..4)
+            .map(|i| {
+                (0..3)
+                    .map(|j| {
+                        (
+                            Vector3::new(i as f64, j as f64, ((i + j) % 2) as f64),
+                            1.0 + ((i * j) % 3) as f64,
+                        )
+                    })
+                    .collect()
+            })
+            .collect(),
+        2,
+        2,
+    );
+    let (bottom, top) = surface.split_u(0.3).unwrap();
+    let (left, right) = surface.split_v(0.6).unwrap();
+    let sub = surface.sub_surface(0.25..0.75, 0.0..0.5).unwrap();
+    for i in 0..=10 {
+        for j in 0..=10 {
+            let (u, v) = (i as f64 / 10.0, j as f64 / 10.0);
+            let a = surface.get_point(u, v);
+            let mut parts = vec![
+                if u <= 0.3 { &bottom } else { &top },
+                if v <= 0.6 { &left } else { &right },
+            ];
+            if (0.25..=0.75).contains(&u) && v <= 0.5 {
+                parts.push(&sub);
+            }
+            for part in parts {
+                let b = part.get_point(u, v);
+                assert_approx_eq!(a.x, b.x);
+                assert_approx_eq!(a.y, b.y);
+                assert_approx_eq!(a.z, b.z);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_nurbs_surface_iso_curve() {
+    use crate::basis_function::BasisFunctionError;
+    use crate::curve::RationalCurve;
+    use crate::surface::{ParametricSurface, RationalSurface};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector3;
+    let surface = NurbsSurface::new_uniform(
+        (0..4)
+            .map(|i| {
+                (0..3)
+                    .map(|j| {
+                        (
+                            Vector3::new(i as f64, j as f64, ((i + j) % 2) as f64),
+                            1.0 + ((i * j) % 3) as f64,
+                        )
+                    })
+                    .collect()
+            })
+            .collect(),
+        2,
+        2,
+    );
+    for i in 0..=10 {
+        let c = i as f64 / 10.0;
+        let u_curve = surface.iso_curve_u(c).unwrap();
+        let v_curve = surface.iso_curve_v(c).unwrap();
+        for j in 0..=10 {
+            let t = j as f64 / 10.0;
+            for (a, b) in [
+                (surface.get_point(c, t), u_curve.get_point(t)),
+                (surface.get_point(t, c), v_curve.get_point(t)),
+            ] {
+                assert_approx_eq!(a.x, b.x);
+                assert_approx_eq!(a.y, b.y);
+                assert_approx_eq!(a.z, b.z);
+            }
+        }
+    }
+    assert!(matches!(
+        surface.iso_curve_u(1.5),
+        Err(BasisFunctionError::UGreaterThanMax)
+    ));
+    assert!(matches!(
+        surface.iso_curve_u(f64::NAN),
+        Err(BasisFunctionError::UIsNon)
+    ));
+    assert!(surface.iso_curve_v(2.0).is_err());
+}
+
+#[test]
+fn test_nurbs_surface_closest_point() {
+    use crate::surface::RationalSurface;
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector3;
+    // quarter of cylinder with radius 2 and height 3
+    let w = 0.5f64.sqrt();
+    let surface = NurbsSurface::new_uniform(
+        vec![
+            vec![
+                (Vector3::new(2., 0., 0.), 1.),
+                (Vector3::new(2., 0., 3.), 1.),
+            ],
+            vec![(Vector3::new(2., 2., 0.), w), (Vector3::new(2., 2., 3.), w)],
+            vec![
+                (Vector3::new(0., 2., 0.), 1.),
+                (Vector3::new(0., 2., 3.), 1.),
+            ],
+        ],
+        2,
+        1,
+    );
+    for i in 1..10 {
+        let angle = std::f64::consts::FRAC_PI_2 * i as f64 / 10.0;
+        for (r, z) in [(0.5, 0.3), (1.9, 1.5), (2.0, 2.0), (3.0, 2.9)] {
+            let p = Vector3::new(angle.cos() * r, angle.sin() * r, z);
+            let (u, v, point, distance) = surface.closest_point(&p).unwrap();
+            assert_approx_eq!(point.x, angle.cos() * 2.0, 1e-8);
+            assert_approx_eq!(point.y, angle.sin() * 2.0, 1e-8);
+            assert_approx_eq!(point.z, z, 1e-8);
+            assert_approx_eq!(v, z / 3.0, 1e-8);
+            assert_approx_eq!(distance, (r - 2.0f64).abs(), 1e-8);
+            let on_surface = surface.get_point(u, v);
+            assert_approx_eq!(on_surface.x, point.x);
+            assert_approx_eq!(on_surface.y, point.y);
+        }
+    }
+    // above the top the closest point is on the top edge
+    let (_, v, point, distance) = surface
+        .closest_point(&Vector3::new(3.0f64.sqrt(), 1.0, 4.0))
+        .unwrap();
+    assert_eq!(v, 1.0);
+    assert_approx_eq!(point.z, 3.0);
+    assert_approx_eq!(distance, 1.0);
+}
+
+#[test]
+fn test_nurbs_surface_reparametrize_reverse() {
+    use crate::curve::ParametricCurve;
+    use crate::surface::{ParametricSurface, RationalSurface};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector3;
+    let surface = NurbsSurface::new_uniform(
+        (0..4)
+            .map(|i| {
+                (0..3)
+                    .map(|j| {
+                        (
+                            Vector3::new(i as f64, j as f64, ((i + j) % 2) as f64),
+                            1.0 + ((i * j) % 3) as f64,
+                        )
+                    })
+                    .collect()
+            })
+            .collect(),
+        2,
+        2,
+    );
+    let moved = surface.reparametrize(2.5..17.0, -1.0..1.0).unwrap();
+    assert_eq!(moved.domain(), ((2.5, 17.0), (-1.0, 1.0)));
+    assert_eq!(moved.iso_curve_v(0.0).unwrap().domain(), (2.5, 17.0));
+    let reversed = moved.reverse_u().reverse_v();
+    assert_eq!(reversed.domain(), moved.domain());
+    for i in 0..=10 {
+        let s = i as f64 / 10.0;
+        let u = 2.5 + 14.5 * s;
+        for j in 0..=10 {
+            let t = j as f64 / 10.0;
+            let v = -1.0 + 2.0 * t;
+            for (a, b) in [
+                (surface.get_point(s, t), moved.get_point(u, v)),
+                (moved.get_point(u, v), reversed.get_point(19.5 - u, -v)),
+            ] {
+                assert_approx_eq!(a.x, b.x);
+                assert_approx_eq!(a.y, b.y);
+                assert_approx_eq!(a.z, b.z);
+            }
+        }
+    }
+    assert!(surface.reparametrize(0.0..1.0, 1.0..1.0).is_err());
+}
+
+#[test]
+fn test_nurbs_surface_get_ders_shape() {
+    use crate::surface::RationalSurface;
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector3;
+    // quarter of cylinder with radius 2 and height 3
+    let w = 0.5f64.sqrt();
+    let surface = NurbsSurface::new_uniform(
+        vec![
+            vec![
+                (Vector3::new(2., 0., 0.), 1.),
+                (Vector3::new(2., 0., 3.), 1.),
+            ],
+            vec![(Vector3::new(2., 2., 0.), w), (Vector3::new(2., 2., 3.), w)],
+            vec![
+                (Vector3::new(0., 2., 0.), 1.),
+                (Vector3::new(0., 2., 3.), 1.),
+            ],
+        ],
+        2,
+        1,
+    );
+    let (u, v) = (0.4, 0.7);
+    // SKL[k][l] for every k + l <= 3, zeros above the total order
+    let ders = surface.get_ders(3, u, v);
+    assert_eq!(ders.len(), 4);
+    assert!(ders.iter().all(|row| row.len() == 4));
+    for (k, row) in ders.iter().enumerate() {
+        for (l, der) in row.iter().enumerate() {
+            if k + l > 3 {
+                assert_eq!(*der, Vector3::zeros());
+            }
+        }
+    }
+    // the surface is linear in v and a circle of radius 2 in u
+    assert_approx_eq!(ders[0][0].xy().norm(), 2.0);
+    assert_approx_eq!(ders[0][0].z, 3.0 * v);
+    assert_approx_eq!(ders[0][1].z, 3.0);
+    assert_approx_eq!(ders[0][0].xy().dot(&ders[1][0].xy()), 0.0);
+    for der in &ders[0][2..] {
+        assert_eq!(*der, Vector3::zeros());
+    }
+}
@@ -0,0 +1,136 @@
+use nalgebra::SVector;
+
+use crate::curve::RationalBezierCurveBase;
+
+pub type RationalBezierCurve<const D: usize> = RationalBezierCurveBase<SVector<f64, D>>;
+
+#[test]
+fn test_rational_bezier_curve_get_point() {
+    use crate::curve::RationalCurve;
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    // quarter of unit circle
+    let curve = RationalBezierCurve::<2>::new(vec![
+        (Vector2::new(1.0, 0.0), 1.0),
+        (Vector2::new(1.0, 1.0), 0.5f64.sqrt()),
+        (Vector2::new(0.0, 1.0), 1.0),
+    ]);
+    for i in 0..=10 {
+        let p = curve.get_point(i as f64 / 10.0);
+        assert_approx_eq!(p.norm(), 1.0);
+    }
+    assert_approx_eq!(curve.get_point(0.5).x, 0.5f64.sqrt());
+    assert_approx_eq!(curve.get_point(0.5).y, 0.5f64.sqrt());
+}
+
+#[test]
+fn test_rational_bezier_curve_get_ders() {
+    use crate::curve::{ParametricCurve, RationalCurve};
+    use nalgebra::Vector2;
+    let curve = RationalBezierCurve::<2>::new(vec![
+        (Vector2::new(1.0, 0.0), 1.0),
+        (Vector2::new(1.0, 1.0), 1.0),
+        (Vector2::new(0.0, 1.0), 2.0),
+    ]);
+    assert_eq!(
+        curve.get_ders(curve.degree(), 0.0),
+        vec![
+            Vector2::new(1.0, 0.0),
+            Vector2::new(0.0, 2.0),
+            Vector2::new(-4.0, 0.0)
+        ]
+    );
+}
+
+#[test]
+fn test_rational_bezier_curve_elevate_degree() {
+    use crate::curve::{ParametricCurve, RationalCurve};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    let curve = RationalBezierCurve::<2>::new(vec![
+        (Vector2::new(1.0, 0.0), 1.0),
+        (Vector2::new(1.0, 1.0), 0.5f64.sqrt()),
+        (Vector2::new(0.0, 1.0), 1.0),
+    ]);
+    let elevated = curve.elevate_degree(2);
+    assert_eq!(elevated.degree(), 4);
+    for i in 0..=10 {
+        let u = i as f64 / 10.0;
+        let a = curve.get_point(u);
+        let b = elevated.get_point(u);
+        assert_approx_eq!(a.x, b.x);
+        assert_approx_eq!(a.y, b.y);
+    }
+}
+
+#[test]
+fn test_rational_bezier_curve_split() {
+    use crate::curve::RationalCurve;
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    let curve = RationalBezierCurve::<2>::new(vec![
+        (Vector2::new(1.0, 0.0), 1.0),
+        (Vector2::new(1.0, 1.0), 0.5f64.sqrt()),
+        (Vector2::new(0.0, 1.0), 1.0),
+    ]);
+    let (left, right) = curve.split_at(0.5).unwrap();
+    let sub = curve.sub_curve(0.25, 0.75).unwrap();
+    for i in 0..=10 {
+        let t = i as f64 / 10.0;
+        for (a, b) in [
+            (curve.get_point(0.5 * t), left.get_point(0.5 * t)),
+            (
+                curve.get_point(0.5 + 0.5 * t),
+                right.get_point(0.5 + 0.5 * t),
+            ),
+            (
+                curve.get_point(0.25 + 0.5 * t),
+                sub.get_point(0.25 + 0.5 * t),
+            ),
+        ] {
+            assert_approx_eq!(a.x, b.x);
+            assert_approx_eq!(a.y, b.y);
+        }
+    }
+}
+
+#[test]
+fn test_rational_bezier_curve_domain() {
+    use crate::basis_function::BasisFunctionError;
+    use crate::curve::{ParametricCurve, RationalCurve};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector2;
+    let control_points = vec![
+        (Vector2::new(1.0, 0.0), 1.0),
+        (Vector2::new(1.0, 1.0), 0.5f64.sqrt()),
+        (Vector2::new(0.0, 1.0), 1.0),
+    ];
+    let curve = RationalBezierCurve::<2>::new(control_points.clone());
+    let moved = RationalBezierCurve::new_on(control_points, -1.0, 3.0).unwrap();
+    assert_eq!(moved.domain(), (-1.0, 3.0));
+    let reversed = moved.reverse();
+    let (left, right) = moved.split_at(1.0).unwrap();
+    assert_eq!(left.domain(), (-1.0, 1.0));
+    assert_eq!(right.domain(), (1.0, 3.0));
+    for i in 0..=10 {
+        let t = i as f64 / 10.0;
+        let u = -1.0 + 4.0 * t;
+        for (a, b) in [
+            (curve.get_point(t), moved.get_point(u)),
+            (curve.get_point(1.0 - t), reversed.get_point(u)),
+            (curve.get_point(0.5 * t), left.get_point(-1.0 + 2.0 * t)),
+        ] {
+            assert_approx_eq!(a.x, b.x);
+            assert_approx_eq!(a.y, b.y);
+        }
+        assert_approx_eq!(moved.get_point(u).norm(), 1.0);
+    }
+    assert!(matches!(
+        RationalBezierCurve::<2>::new_on(vec![], 0.0, 1.0),
+        Err(BasisFunctionError::TooFewPoints)
+    ));
+    assert!(matches!(
+        RationalBezierCurve::new_on(vec![(Vector2::new(1.0, 0.0), 0.0)], 0.0, 1.0),
+        Err(BasisFunctionError::NonPositiveWeight)
+    ));
+}
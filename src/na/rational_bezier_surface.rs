@@ -0,0 +1,106 @@
+use nalgebra::SVector;
+
+use crate::surface::RationalBezierSurfaceBase;
+
+pub type RationalBezierSurface<const D: usize> = RationalBezierSurfaceBase<SVector<f64, D>>;
+
+#[test]
+fn test_rational_bezier_surface_get_point() {
+    use crate::surface::{ParametricSurface, RationalSurface};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector3;
+    // quarter of cylinder with radius 2 and height 3
+    let w = 0.5f64.sqrt();
+    let surface = RationalBezierSurface::<3>::new(vec![
+        vec![(Vector3::new(2., 0., 0.), 1.), (Vector3::new(2., 0., 3.), 1.)],
+        vec![(Vector3::new(2., 2., 0.), w), (Vector3::new(2., 2., 3.), w)],
+        vec![(Vector3::new(0., 2., 0.), 1.), (Vector3::new(0., 2., 3.), 1.)],
+    ]);
+    assert_eq!(surface.p(), 2);
+    assert_eq!(surface.q(), 1);
+    for i in 0..=10 {
+        for j in 0..=10 {
+            let (u, v) = (i as f64 / 10.0, j as f64 / 10.0);
+            let p = surface.get_point(u, v);
+            assert_approx_eq!(p.x.hypot(p.y), 2.0);
+            assert_approx_eq!(p.z, 3.0 * v);
+        }
+    }
+    let elevated = surface.elevate_degree_u(1).elevate_degree_v(2);
+    for i in 0..=10 {
+        for j in 0..=10 {
+            let (u, v) = (i as f64 / 10.0, j as f64 / 10.0);
+            let a = surface.get_point(u, v);
+            let b = elevated.get_point(u, v);
+            assert_approx_eq!(a.x, b.x);
+            assert_approx_eq!(a.y, b.y);
+            assert_approx_eq!(a.z, b.z);
+        }
+    }
+}
+
+#[test]
+fn test_rational_bezier_surface_split() {
+    use crate::surface::{ParametricSurface, RationalSurface};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector3;
+    let w = 0.5f64.sqrt();
+    let surface = RationalBezierSurface::<3>::new(vec![
+        vec![(Vector3::new(2., 0., 0.), 1.), (Vector3::new(2., 0., 3.), 1.)],
+        vec![(Vector3::new(2., 2., 0.), w), (Vector3::new(2., 2., 3.), w)],
+        vec![(Vector3::new(0., 2., 0.), 1.), (Vector3::new(0., 2., 3.), 1.)],
+    ]);
+    let (bottom, top) = surface.split_u(0.5).unwrap();
+    let sub = surface.sub_surface(0.25..0.5, 0.2..0.8).unwrap();
+    assert_eq!(bottom.domain(), ((0.0, 0.5), (0.0, 1.0)));
+    assert_eq!(top.domain(), ((0.5, 1.0), (0.0, 1.0)));
+    assert_eq!(sub.domain(), ((0.25, 0.5), (0.2, 0.8)));
+    for i in 0..=5 {
+        for j in 0..=5 {
+            let (s, t) = (i as f64 / 5.0, j as f64 / 5.0);
+            let (u, v) = (0.25 + 0.25 * s, 0.2 + 0.6 * t);
+            for (a, b) in [
+                (surface.get_point(0.5 * s, t), bottom.get_point(0.5 * s, t)),
+                (
+                    surface.get_point(0.5 + 0.5 * s, t),
+                    top.get_point(0.5 + 0.5 * s, t),
+                ),
+                (surface.get_point(u, v), sub.get_point(u, v)),
+            ] {
+                assert_approx_eq!(a.x, b.x);
+                assert_approx_eq!(a.y, b.y);
+                assert_approx_eq!(a.z, b.z);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_rational_bezier_surface_iso_curve() {
+    use crate::curve::RationalCurve;
+    use crate::surface::{ParametricSurface, RationalSurface};
+    use assert_approx_eq::assert_approx_eq;
+    use nalgebra::Vector3;
+    let w = 0.5f64.sqrt();
+    let surface = RationalBezierSurface::<3>::new(vec![
+        vec![(Vector3::new(2., 0., 0.), 1.), (Vector3::new(2., 0., 3.), 1.)],
+        vec![(Vector3::new(2., 2., 0.), w), (Vector3::new(2., 2., 3.), w)],
+        vec![(Vector3::new(0., 2., 0.), 1.), (Vector3::new(0., 2., 3.), 1.)],
+    ]);
+    // constant v gives an exact circular arc
+    let arc = surface.iso_curve_v(0.5).unwrap();
+    for i in 0..=10 {
+        let p = arc.get_point(i as f64 / 10.0);
+        assert_approx_eq!(p.x.hypot(p.y), 2.0);
+        assert_approx_eq!(p.z, 1.5);
+    }
+    let line = surface.iso_curve_u(0.3).unwrap();
+    for i in 0..=10 {
+        let t = i as f64 / 10.0;
+        let a = surface.get_point(0.3, t);
+        let b = line.get_point(t);
+        assert_approx_eq!(a.x, b.x);
+        assert_approx_eq!(a.y, b.y);
+        assert_approx_eq!(a.z, b.z);
+    }
+}
@@ -1,11 +1,20 @@
 use crate::{
-    basics::{algorithm::de_casteljaul, ControlPoint},
-    basis_function::BernsteinBasis,
+    basics::{
+        algorithm::{
+            bezier_surface_degree_elevate_u, bezier_surface_degree_elevate_v,
+            bezier_surface_split_u, bezier_surface_split_v, check_interval, de_casteljaul_surface,
+            get_surface_iso_curve_u, get_surface_iso_curve_v,
+        },
+        ControlPoint,
+    },
+    basis_function::{Basis, BasisFunctionError, BernsteinBasis},
+    curve::BezierCurveBase,
 };
+use std::ops::Range;
 
-use super::{NonRationalSurface, ParametricSurface};
+use super::{check_control_net, NonRationalSurface, ParametricSurface};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BezierSurfaceBase<P>
 where
     P: ControlPoint,
@@ -26,10 +35,164 @@ where
             control_points,
         }
     }
+
+    /// error if control_points is empty or its rows differ in length
+    pub fn try_new(control_points: Vec<Vec<P>>) -> Result<Self, BasisFunctionError> {
+        check_control_net(&control_points)?;
+        Ok(Self::new(control_points))
+    }
+
+    /// surface on domain u x v instead of \[0, 1] x \[0, 1]
+    ///
+    /// error if control_points is empty or ragged, or u or v is empty or not finite
+    pub fn new_on(
+        control_points: Vec<Vec<P>>,
+        u: Range<f64>,
+        v: Range<f64>,
+    ) -> Result<Self, BasisFunctionError> {
+        Self::try_new(control_points)?.reparametrize(u, v)
+    }
+
+    pub(crate) fn from_basis(
+        u_basis_function: BernsteinBasis,
+        v_basis_function: BernsteinBasis,
+        control_points: Vec<Vec<P>>,
+    ) -> Self {
+        Self {
+            u_basis_function,
+            v_basis_function,
+            control_points,
+        }
+    }
+
+    /// surface on the same domain with other control points
+    fn with_control_points(&self, control_points: Vec<Vec<P>>) -> Self {
+        Self {
+            u_basis_function: self.u_basis_function.with_len(control_points.len()),
+            v_basis_function: self.v_basis_function.with_len(control_points[0].len()),
+            control_points,
+        }
+    }
+
+    /// raise degree in u direction from p to p + t without changing the shape of surface
+    pub fn elevate_degree_u(&self, t: usize) -> Self {
+        self.with_control_points(bezier_surface_degree_elevate_u(&self.control_points, t))
+    }
+
+    /// raise degree in v direction from q to q + t without changing the shape of surface
+    pub fn elevate_degree_v(&self, t: usize) -> Self {
+        self.with_control_points(bezier_surface_degree_elevate_v(&self.control_points, t))
+    }
+
+    /// split surface at u into two surfaces on \[a, u] and \[u, b] in u direction,
+    /// \[a, b] is the u domain of surface
+    ///
+    /// error if u is not inside the u domain
+    pub fn split_u(&self, u: f64) -> Result<(Self, Self), BasisFunctionError> {
+        let (a, b) = self.u_basis_function.domain();
+        if u <= a {
+            return Err(BasisFunctionError::ULessThanMin);
+        }
+        if u >= b {
+            return Err(BasisFunctionError::UGreaterThanMax);
+        }
+        let t = self.u_basis_function.to_unit(u);
+        let (left, right) = bezier_surface_split_u(&self.control_points, t);
+        Ok((
+            Self {
+                u_basis_function: BernsteinBasis::with_len_on(left.len(), a, u),
+                v_basis_function: self.v_basis_function.clone(),
+                control_points: left,
+            },
+            Self {
+                u_basis_function: BernsteinBasis::with_len_on(right.len(), u, b),
+                v_basis_function: self.v_basis_function.clone(),
+                control_points: right,
+            },
+        ))
+    }
+
+    /// split surface at v into two surfaces on \[a, v] and \[v, b] in v direction,
+    /// \[a, b] is the v domain of surface
+    ///
+    /// error if v is not inside the v domain
+    pub fn split_v(&self, v: f64) -> Result<(Self, Self), BasisFunctionError> {
+        let (a, b) = self.v_basis_function.domain();
+        if v <= a {
+            return Err(BasisFunctionError::ULessThanMin);
+        }
+        if v >= b {
+            return Err(BasisFunctionError::UGreaterThanMax);
+        }
+        let t = self.v_basis_function.to_unit(v);
+        let (left, right) = bezier_surface_split_v(&self.control_points, t);
+        Ok((
+            Self {
+                u_basis_function: self.u_basis_function.clone(),
+                v_basis_function: BernsteinBasis::with_len_on(left[0].len(), a, v),
+                control_points: left,
+            },
+            Self {
+                u_basis_function: self.u_basis_function.clone(),
+                v_basis_function: BernsteinBasis::with_len_on(right[0].len(), v, b),
+                control_points: right,
+            },
+        ))
+    }
+
+    /// extract the part of surface on u x v
+    ///
+    /// error if u or v is not a valid interval inside the domain
+    pub fn sub_surface(&self, u: Range<f64>, v: Range<f64>) -> Result<Self, BasisFunctionError> {
+        let ((ua, ub), (va, vb)) = self.domain();
+        check_interval(&u, ua, ub)?;
+        check_interval(&v, va, vb)?;
+        let mut surface = self.clone();
+        if u.end < ub {
+            surface = surface.split_u(u.end)?.0;
+        }
+        if u.start > ua {
+            surface = surface.split_u(u.start)?.1;
+        }
+        if v.end < vb {
+            surface = surface.split_v(v.end)?.0;
+        }
+        if v.start > va {
+            surface = surface.split_v(v.start)?.1;
+        }
+        Ok(surface)
+    }
+
+    /// same surface on domain u x v
+    ///
+    /// error if u or v is empty or not finite
+    pub fn reparametrize(&self, u: Range<f64>, v: Range<f64>) -> Result<Self, BasisFunctionError> {
+        Ok(Self {
+            u_basis_function: BernsteinBasis::new_on(self.control_points.len(), u.start, u.end)?,
+            v_basis_function: BernsteinBasis::new_on(self.control_points[0].len(), v.start, v.end)?,
+            control_points: self.control_points.clone(),
+        })
+    }
+
+    /// same surface with u direction reversed on the same domain
+    pub fn reverse_u(&self) -> Self {
+        self.with_control_points(self.control_points.iter().rev().cloned().collect())
+    }
+
+    /// same surface with v direction reversed on the same domain
+    pub fn reverse_v(&self) -> Self {
+        self.with_control_points(
+            self.control_points
+                .iter()
+                .map(|row| row.iter().rev().copied().collect())
+                .collect(),
+        )
+    }
 }
 
 impl<P: ControlPoint> ParametricSurface<P> for BezierSurfaceBase<P> {
     type BasisFunction = BernsteinBasis;
+    type IsoCurve = BezierCurveBase<P>;
 
     fn u_basis_function(&self) -> &Self::BasisFunction {
         &self.u_basis_function
@@ -42,6 +205,20 @@ impl<P: ControlPoint> ParametricSurface<P> for BezierSurfaceBase<P> {
     fn control_points(&self) -> &Vec<Vec<P>> {
         &self.control_points
     }
+
+    fn iso_curve_u(&self, u: f64) -> Result<Self::IsoCurve, BasisFunctionError> {
+        Ok(BezierCurveBase::from_basis(
+            self.v_basis_function.clone(),
+            get_surface_iso_curve_u(&self.u_basis_function, &self.control_points, u)?,
+        ))
+    }
+
+    fn iso_curve_v(&self, v: f64) -> Result<Self::IsoCurve, BasisFunctionError> {
+        Ok(BezierCurveBase::from_basis(
+            self.u_basis_function.clone(),
+            get_surface_iso_curve_v(&self.v_basis_function, &self.control_points, v)?,
+        ))
+    }
 }
 
 impl<P: ControlPoint> NonRationalSurface<P> for BezierSurfaceBase<P>
@@ -50,20 +227,10 @@ where
 {
     /// get geometry point from parameter u & v
     fn get_point(&self, u: f64, v: f64) -> P {
-        let n = self.p();
-        let m = self.q();
-        if n <= m {
-            let mut q = vec![P::zeros(); m + 1];
-            for j in 0..=m {
-                q[j] = de_casteljaul(n, u, &self.control_points[..][j]);
-            }
-            return de_casteljaul(m, v, &q);
-        } else {
-            let mut q = vec![P::zeros(); n + 1];
-            for i in 0..=n {
-                q[i] = de_casteljaul(m, v, &self.control_points[i]);
-            }
-            return de_casteljaul(n, u, &q);
-        }
+        de_casteljaul_surface(
+            &self.control_points,
+            self.u_basis_function.to_unit(u),
+            self.v_basis_function.to_unit(v),
+        )
     }
 }
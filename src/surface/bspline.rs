@@ -1,7 +1,22 @@
-use crate::{basics::ControlPoint, basis_function::BsplineBasis};
+use crate::{
+    basics::{
+        algorithm::{
+            check_interval, get_surface_iso_curve_u, get_surface_iso_curve_v, surface_clamp_u,
+            surface_clamp_v, surface_decompose, surface_degree_elevate_u, surface_degree_elevate_v,
+            surface_fit_least_squares, surface_fit_scattered, surface_interpolate,
+            surface_knot_refine_u, surface_knot_refine_v, surface_split_u, surface_split_v,
+            uniform_basis,
+        },
+        ControlPoint, Deviation, InnerProductSpace, Parameterization,
+    },
+    basis_function::{check_basis, Basis, BasisFunctionError, BernsteinBasis, BsplineBasis, Knots},
+    curve::BsplineCurveBase,
+};
+use std::ops::Range;
 
-use super::{NonRationalSurface, ParametricSurface};
+use super::{check_control_net, BezierSurfaceBase, NonRationalSurface, ParametricSurface};
 
+#[derive(Debug, Clone)]
 pub struct BsplineSurfaceBase<P>
 where
     P: ControlPoint,
@@ -15,20 +30,412 @@ impl<P> BsplineSurfaceBase<P>
 where
     P: ControlPoint,
 {
-    /// TODO better error handle, unwarp() is bad
-    /// 
-    /// TODO refine all error handle
+    pub fn new(
+        control_points: Vec<Vec<P>>,
+        u_degree: usize,
+        u_knot: Vec<f64>,
+        v_degree: usize,
+        v_knot: Vec<f64>,
+    ) -> Self {
+        Self {
+            u_basis_function: BsplineBasis::new(u_degree, Knots::new(u_knot)),
+            v_basis_function: BsplineBasis::new(v_degree, Knots::new(v_knot)),
+            control_points,
+        }
+    }
+
+    /// error if control net is empty or ragged, knots are not finite and non-decreasing,
+    /// a degree is too large for control net
+    /// or number of knots != number of control points + degree + 1 in either direction
+    pub fn try_new(
+        control_points: Vec<Vec<P>>,
+        u_degree: usize,
+        u_knot: Vec<f64>,
+        v_degree: usize,
+        v_knot: Vec<f64>,
+    ) -> Result<Self, BasisFunctionError> {
+        let n_v = check_control_net(&control_points)?;
+        let surface = Self::new(control_points, u_degree, u_knot, v_degree, v_knot);
+        check_basis(&surface.u_basis_function, surface.control_points.len())?;
+        check_basis(&surface.v_basis_function, n_v)?;
+        Ok(surface)
+    }
+
+    /// panic if control net is empty, ragged or too small for degrees, see `try_new_uniform`
     pub fn new_uniform(control_points: Vec<Vec<P>>, u_degree: usize, v_degree: usize) -> Self {
+        Self::try_new_uniform(control_points, u_degree, v_degree).unwrap()
+    }
+
+    /// error if control_points is empty, its rows differ in length,
+    /// or it has less than u_degree + 1 rows or v_degree + 1 columns
+    pub fn try_new_uniform(
+        control_points: Vec<Vec<P>>,
+        u_degree: usize,
+        v_degree: usize,
+    ) -> Result<Self, BasisFunctionError> {
+        let n_v = check_control_net(&control_points)?;
+        Ok(Self {
+            u_basis_function: BsplineBasis::new_uniform(u_degree, control_points.len())?,
+            v_basis_function: BsplineBasis::new_uniform(v_degree, n_v)?,
+            control_points,
+        })
+    }
+
+    /// insert all knots in x at once into u knot vector without changing the shape of surface,
+    /// x should be sorted in non-decreasing order
+    pub fn refine_knots_u(&self, x: &[f64]) -> Result<Self, BasisFunctionError> {
+        let p = self.p();
+        let (knots, control_points) =
+            surface_knot_refine_u(p, self.u_basis_function.knots(), &self.control_points, x)?;
+        Ok(Self {
+            u_basis_function: BsplineBasis::new(p, knots),
+            v_basis_function: self.v_basis_function.clone(),
+            control_points,
+        })
+    }
+
+    /// insert all knots in x at once into v knot vector without changing the shape of surface,
+    /// x should be sorted in non-decreasing order
+    pub fn refine_knots_v(&self, x: &[f64]) -> Result<Self, BasisFunctionError> {
+        let q = self.q();
+        let (knots, control_points) =
+            surface_knot_refine_v(q, self.v_basis_function.knots(), &self.control_points, x)?;
+        Ok(Self {
+            u_basis_function: self.u_basis_function.clone(),
+            v_basis_function: BsplineBasis::new(q, knots),
+            control_points,
+        })
+    }
+
+    /// raise degree in u direction from p to p + t without changing the shape of surface,
+    /// a surface unclamped in u direction is clamped first
+    pub fn elevate_degree_u(&self, t: usize) -> Self {
+        let p = self.p();
+        if !self.u_basis_function.knots().is_clamped(p) {
+            return self.clamp_u().elevate_degree_u(t);
+        }
+        let (knots, control_points) =
+            surface_degree_elevate_u(p, self.u_basis_function.knots(), &self.control_points, t);
+        Self {
+            u_basis_function: BsplineBasis::new(p + t, knots),
+            v_basis_function: self.v_basis_function.clone(),
+            control_points,
+        }
+    }
+
+    /// raise degree in v direction from q to q + t without changing the shape of surface,
+    /// a surface unclamped in v direction is clamped first
+    pub fn elevate_degree_v(&self, t: usize) -> Self {
+        let q = self.q();
+        if !self.v_basis_function.knots().is_clamped(q) {
+            return self.clamp_v().elevate_degree_v(t);
+        }
+        let (knots, control_points) =
+            surface_degree_elevate_v(q, self.v_basis_function.knots(), &self.control_points, t);
         Self {
-            u_basis_function: BsplineBasis::new_uniform(u_degree, control_points.len()).unwrap(),
-            v_basis_function: BsplineBasis::new_uniform(v_degree, control_points[0].len()).unwrap(),
+            u_basis_function: self.u_basis_function.clone(),
+            v_basis_function: BsplineBasis::new(q + t, knots),
             control_points,
         }
     }
 
+    /// decompose surface into bezier patches, indexed by \[u segment]\[v segment],
+    /// the patch (i, j) is on the i-th nonempty u knot span x the j-th nonempty v knot span,
+    /// a surface unclamped in either direction is clamped first
+    pub fn decompose(&self) -> Vec<Vec<BezierSurfaceBase<P>>> {
+        if !self.u_basis_function.knots().is_clamped(self.p()) {
+            return self.clamp_u().decompose();
+        }
+        if !self.v_basis_function.knots().is_clamped(self.q()) {
+            return self.clamp_v().decompose();
+        }
+        let u_breakpoints = self.u_basis_function.knots().breakpoints(self.p());
+        let v_breakpoints = self.v_basis_function.knots().breakpoints(self.q());
+        surface_decompose(
+            self.p(),
+            self.u_basis_function.knots(),
+            self.q(),
+            self.v_basis_function.knots(),
+            &self.control_points,
+        )
+        .into_iter()
+        .zip(u_breakpoints.windows(2))
+        .map(|(strip, u)| {
+            strip
+                .into_iter()
+                .zip(v_breakpoints.windows(2))
+                .map(|(patch, v)| {
+                    BezierSurfaceBase::from_basis(
+                        BernsteinBasis::with_len_on(patch.len(), u[0], u[1]),
+                        BernsteinBasis::with_len_on(patch[0].len(), v[0], v[1]),
+                        patch,
+                    )
+                })
+                .collect()
+        })
+        .collect()
+    }
+
+    /// split surface at u into two surfaces on \[u_p, u] and \[u, u_m-p] in u direction
+    ///
+    /// error if u is not inside the domain of surface
+    pub fn split_u(&self, u: f64) -> Result<(Self, Self), BasisFunctionError> {
+        let p = self.p();
+        let ((left_knots, left), (right_knots, right)) =
+            surface_split_u(p, self.u_basis_function.knots(), &self.control_points, u)?;
+        Ok((
+            Self {
+                u_basis_function: BsplineBasis::new(p, left_knots),
+                v_basis_function: self.v_basis_function.clone(),
+                control_points: left,
+            },
+            Self {
+                u_basis_function: BsplineBasis::new(p, right_knots),
+                v_basis_function: self.v_basis_function.clone(),
+                control_points: right,
+            },
+        ))
+    }
+
+    /// split surface at v into two surfaces on \[v_q, v] and \[v, v_m-q] in v direction
+    ///
+    /// error if v is not inside the domain of surface
+    pub fn split_v(&self, v: f64) -> Result<(Self, Self), BasisFunctionError> {
+        let q = self.q();
+        let ((left_knots, left), (right_knots, right)) =
+            surface_split_v(q, self.v_basis_function.knots(), &self.control_points, v)?;
+        Ok((
+            Self {
+                u_basis_function: self.u_basis_function.clone(),
+                v_basis_function: BsplineBasis::new(q, left_knots),
+                control_points: left,
+            },
+            Self {
+                u_basis_function: self.u_basis_function.clone(),
+                v_basis_function: BsplineBasis::new(q, right_knots),
+                control_points: right,
+            },
+        ))
+    }
+
+    /// extract the part of surface on u x v
+    ///
+    /// error if u or v is not a valid interval inside the domain of surface
+    pub fn sub_surface(&self, u: Range<f64>, v: Range<f64>) -> Result<Self, BasisFunctionError> {
+        let ((u_start, u_end), (v_start, v_end)) = self.domain();
+        check_interval(&u, u_start, u_end)?;
+        check_interval(&v, v_start, v_end)?;
+        let mut surface = self.clone();
+        if u.start > u_start {
+            surface = surface.split_u(u.start)?.1;
+        }
+        if u.end < u_end {
+            surface = surface.split_u(u.end)?.0;
+        }
+        if v.start > v_start {
+            surface = surface.split_v(v.start)?.1;
+        }
+        if v.end < v_end {
+            surface = surface.split_v(v.end)?.0;
+        }
+        Ok(surface)
+    }
+
+    /// same surface on domain u x v, knots are mapped affinely
+    ///
+    /// error if u or v is empty or not finite
+    pub fn reparametrize(&self, u: Range<f64>, v: Range<f64>) -> Result<Self, BasisFunctionError> {
+        let (p, q) = (self.p(), self.q());
+        let u_knots = self
+            .u_basis_function
+            .knots()
+            .reparametrize(p, u.start, u.end)?;
+        let v_knots = self
+            .v_basis_function
+            .knots()
+            .reparametrize(q, v.start, v.end)?;
+        Ok(Self {
+            u_basis_function: BsplineBasis::new(p, u_knots),
+            v_basis_function: BsplineBasis::new(q, v_knots),
+            control_points: self.control_points.clone(),
+        })
+    }
+
+    /// same surface on clamped knots in u direction, domain is unchanged
+    pub fn clamp_u(&self) -> Self {
+        let p = self.p();
+        let (knots, control_points) =
+            surface_clamp_u(p, self.u_basis_function.knots(), &self.control_points);
+        Self {
+            u_basis_function: BsplineBasis::new(p, knots),
+            v_basis_function: self.v_basis_function.clone(),
+            control_points,
+        }
+    }
+
+    /// same surface on clamped knots in v direction, domain is unchanged
+    pub fn clamp_v(&self) -> Self {
+        let q = self.q();
+        let (knots, control_points) =
+            surface_clamp_v(q, self.v_basis_function.knots(), &self.control_points);
+        Self {
+            u_basis_function: self.u_basis_function.clone(),
+            v_basis_function: BsplineBasis::new(q, knots),
+            control_points,
+        }
+    }
+
+    /// same surface with u direction reversed on the same domain
+    pub fn reverse_u(&self) -> Self {
+        let p = self.p();
+        Self {
+            u_basis_function: BsplineBasis::new(p, self.u_basis_function.knots().reverse(p)),
+            v_basis_function: self.v_basis_function.clone(),
+            control_points: self.control_points.iter().rev().cloned().collect(),
+        }
+    }
+
+    /// same surface with v direction reversed on the same domain
+    pub fn reverse_v(&self) -> Self {
+        let q = self.q();
+        Self {
+            u_basis_function: self.u_basis_function.clone(),
+            v_basis_function: BsplineBasis::new(q, self.v_basis_function.knots().reverse(q)),
+            control_points: self
+                .control_points
+                .iter()
+                .map(|row| row.iter().rev().copied().collect())
+                .collect(),
+        }
+    }
+
     /// if this work, user do no need to use trait to use basic algorithms
     pub fn get_point(&self) {
-        <Self as NonRationalSurface<P>>::get_point(&self, 0., 0.);
+        <Self as NonRationalSurface<P>>::get_point(self, 0., 0.);
+    }
+}
+
+impl<P> BsplineSurfaceBase<P>
+where
+    P: ControlPoint + InnerProductSpace,
+{
+    /// global interpolation of a grid of points (NURBS Book A9.4), the surface passes
+    /// through points\[k]\[l] at params from `Parameterization::grid_params`,
+    /// knots are placed by averaging params
+    ///
+    /// error if rows of points differ in length, a degree is 0, points are too few
+    /// for degrees or the linear system is singular
+    pub fn interpolate(
+        points: &[Vec<P>],
+        u_degree: usize,
+        v_degree: usize,
+        parameterization: Parameterization,
+    ) -> Result<Self, BasisFunctionError> {
+        check_control_net(points)?;
+        let (u_params, v_params) = parameterization.grid_params(points);
+        let (u_knots, v_knots, control_points) =
+            surface_interpolate(u_degree, v_degree, points, &u_params, &v_params)?;
+        Ok(Self {
+            u_basis_function: BsplineBasis::new(u_degree, u_knots),
+            v_basis_function: BsplineBasis::new(v_degree, v_knots),
+            control_points,
+        })
+    }
+
+    /// least squares approximation of a grid of points by a n_u * n_v control net,
+    /// points are fitted at params from `Parameterization::grid_params`,
+    /// the four corner points are interpolated
+    ///
+    /// output: (surface, deviation of points from surface at their params)
+    ///
+    /// error if rows of points differ in length, a degree is 0, n_u or n_v is less than
+    /// degree + 1 or more than points in that direction, or the normal equations are singular
+    pub fn fit_least_squares(
+        points: &[Vec<P>],
+        u_degree: usize,
+        v_degree: usize,
+        n_u: usize,
+        n_v: usize,
+        parameterization: Parameterization,
+    ) -> Result<(Self, Deviation), BasisFunctionError> {
+        check_control_net(points)?;
+        let (u_params, v_params) = parameterization.grid_params(points);
+        let (u_knots, v_knots, control_points) =
+            surface_fit_least_squares(u_degree, v_degree, points, &u_params, &v_params, n_u, n_v)?;
+        let surface = Self {
+            u_basis_function: BsplineBasis::new(u_degree, u_knots),
+            v_basis_function: BsplineBasis::new(v_degree, v_knots),
+            control_points,
+        };
+        let samples = u_params
+            .iter()
+            .zip(points)
+            .flat_map(|(&u, row)| v_params.iter().zip(row).map(move |(&v, q)| (u, v, q)));
+        let deviation = Deviation::from_distances(
+            samples.map(|(u, v, q)| NonRationalSurface::get_point(&surface, u, v).distance(q)),
+        );
+        Ok((surface, deviation))
+    }
+
+    /// least squares fitting of scattered points with given or estimated params (u_k, v_k),
+    /// knots are uniform on the bounding box of params
+    ///
+    /// smoothing: weight of thin plate bending energy, 0 for no smoothing, a small positive
+    /// value keeps the system well-posed where points are sparse
+    ///
+    /// output: (surface, deviation of points from surface at their params)
+    ///
+    /// error if params and points differ in length, a degree is 0, n_u or n_v is less than
+    /// degree + 1, params span no area, or the normal equations are singular
+    pub fn fit_scattered(
+        points: &[P],
+        params: &[(f64, f64)],
+        u_degree: usize,
+        v_degree: usize,
+        n_u: usize,
+        n_v: usize,
+        smoothing: f64,
+    ) -> Result<(Self, Deviation), BasisFunctionError> {
+        if points.len() != params.len() {
+            return Err(BasisFunctionError::LengthMismatch);
+        }
+        if u_degree == 0 || v_degree == 0 {
+            return Err(BasisFunctionError::TooSmallDegree);
+        }
+        let bound = |f: fn(&(f64, f64)) -> f64| {
+            params
+                .iter()
+                .map(f)
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), x| {
+                    (min.min(x), max.max(x))
+                })
+        };
+        let (u_min, u_max) = bound(|uv| uv.0);
+        let (v_min, v_max) = bound(|uv| uv.1);
+        if !(u_min < u_max && v_min < v_max) {
+            return Err(BasisFunctionError::TooFewPoints);
+        }
+        let u_basis_function = uniform_basis(u_degree, n_u, u_min, u_max)?;
+        let v_basis_function = uniform_basis(v_degree, n_v, v_min, v_max)?;
+        let control_points = surface_fit_scattered(
+            &u_basis_function,
+            &v_basis_function,
+            points,
+            params,
+            smoothing,
+        )?;
+        let surface = Self {
+            u_basis_function,
+            v_basis_function,
+            control_points,
+        };
+        let deviation = Deviation::from_distances(
+            points
+                .iter()
+                .zip(params)
+                .map(|(q, &(u, v))| NonRationalSurface::get_point(&surface, u, v).distance(q)),
+        );
+        Ok((surface, deviation))
     }
 }
 
@@ -37,6 +444,7 @@ where
     P: ControlPoint,
 {
     type BasisFunction = BsplineBasis;
+    type IsoCurve = BsplineCurveBase<P>;
 
     fn u_basis_function(&self) -> &Self::BasisFunction {
         &self.u_basis_function
@@ -49,6 +457,20 @@ where
     fn control_points(&self) -> &Vec<Vec<P>> {
         &self.control_points
     }
+
+    fn iso_curve_u(&self, u: f64) -> Result<Self::IsoCurve, BasisFunctionError> {
+        Ok(BsplineCurveBase::from_basis(
+            self.v_basis_function.clone(),
+            get_surface_iso_curve_u(&self.u_basis_function, &self.control_points, u)?,
+        ))
+    }
+
+    fn iso_curve_v(&self, v: f64) -> Result<Self::IsoCurve, BasisFunctionError> {
+        Ok(BsplineCurveBase::from_basis(
+            self.u_basis_function.clone(),
+            get_surface_iso_curve_v(&self.v_basis_function, &self.control_points, v)?,
+        ))
+    }
 }
 
 impl<P> NonRationalSurface<P> for BsplineSurfaceBase<P> where P: ControlPoint {}
@@ -1,9 +1,10 @@
 use crate::{
     basics::{
-        algorithm::{get_surface_ders, get_surface_point},
-        ControlPoint, HomoControlPoint,
+        algorithm::{get_surface_ders, get_surface_point, surface_closest_point},
+        ControlPoint, HomoControlPoint, InnerProductSpace,
     },
-    basis_function::Basis,
+    basis_function::{check_basis, Basis, BasisFunctionError},
+    curve::ParametricCurve,
 };
 
 use num::integer::binomial;
@@ -11,16 +12,20 @@ use num::integer::binomial;
 mod bezier;
 mod bspline;
 mod nurbs;
+mod rational_bezier;
 
 pub use bezier::BezierSurfaceBase;
 pub use bspline::BsplineSurfaceBase;
 pub use nurbs::NurbsSurfaceBase;
+pub use rational_bezier::RationalBezierSurfaceBase;
 
 pub trait ParametricSurface<P>
 where
     P: ControlPoint,
 {
     type BasisFunction: Basis;
+    /// curve type which represents isoparametric curves of surface exactly
+    type IsoCurve: ParametricCurve<P>;
     // basis funtion u v
     fn u_basis_function(&self) -> &Self::BasisFunction;
     fn v_basis_function(&self) -> &Self::BasisFunction;
@@ -31,6 +36,52 @@ where
     fn q(&self) -> usize {
         self.v_basis_function().degree()
     }
+
+    /// parameter domain ((u start, u end), (v start, v end)) of surface
+    fn domain(&self) -> ((f64, f64), (f64, f64)) {
+        (
+            self.u_basis_function().domain(),
+            self.v_basis_function().domain(),
+        )
+    }
+
+    /// isoparametric curve on constant u, parameterized by v
+    ///
+    /// error if u is outside the domain
+    fn iso_curve_u(&self, u: f64) -> Result<Self::IsoCurve, BasisFunctionError>;
+
+    /// isoparametric curve on constant v, parameterized by u
+    ///
+    /// error if v is outside the domain
+    fn iso_curve_v(&self, v: f64) -> Result<Self::IsoCurve, BasisFunctionError>;
+
+    /// error if knots or control net are invalid, or (u, v) is outside the domain
+    fn check_parameter(&self, u: f64, v: f64) -> Result<(), BasisFunctionError> {
+        let control_points = self.control_points();
+        let n_v = check_control_net(control_points)?;
+        check_basis(self.u_basis_function(), control_points.len())?;
+        check_basis(self.v_basis_function(), n_v)?;
+        self.u_basis_function().try_get_span(u)?;
+        self.v_basis_function().try_get_span(v)?;
+        Ok(())
+    }
+}
+
+/// number of columns of control net
+///
+/// error if control net is empty or its rows differ in length
+pub(crate) fn check_control_net<P>(control_points: &[Vec<P>]) -> Result<usize, BasisFunctionError> {
+    let n_v = control_points
+        .first()
+        .ok_or(BasisFunctionError::TooFewPoints)?
+        .len();
+    if n_v == 0 {
+        return Err(BasisFunctionError::TooFewPoints);
+    }
+    if control_points.iter().any(|row| row.len() != n_v) {
+        return Err(BasisFunctionError::RaggedControlNet);
+    }
+    Ok(n_v)
 }
 
 pub trait NonRationalSurface<P>: ParametricSurface<P>
@@ -48,6 +99,9 @@ where
         )
     }
 
+    /// get derivatives SKL\[k]\[l] = d^(k+l) S / du^k dv^l at (u, v) for k + l <= der_upper_bond,
+    /// output is (der_upper_bond + 1) x (der_upper_bond + 1),
+    /// entries with k + l > der_upper_bond, k > p or l > q are zeros
     fn get_ders(&self, der_upper_bond: usize, u: f64, v: f64) -> Vec<Vec<P>> {
         get_surface_ders(
             self.u_basis_function(),
@@ -58,6 +112,46 @@ where
             v,
         )
     }
+
+    /// same as `get_point` but never panics
+    ///
+    /// error if knots or control net are invalid, or (u, v) is outside the domain
+    fn try_get_point(&self, u: f64, v: f64) -> Result<P, BasisFunctionError> {
+        self.check_parameter(u, v)?;
+        Ok(self.get_point(u, v))
+    }
+
+    /// same as `get_ders` but never panics
+    ///
+    /// error if knots or control net are invalid, or (u, v) is outside the domain
+    fn try_get_ders(
+        &self,
+        der_upper_bond: usize,
+        u: f64,
+        v: f64,
+    ) -> Result<Vec<Vec<P>>, BasisFunctionError> {
+        self.check_parameter(u, v)?;
+        Ok(self.get_ders(der_upper_bond, u, v))
+    }
+
+    /// closest point on surface to p (NURBS Book 6.1), the nearest of grid sampled points
+    /// is refined by 2D newton iteration, parameters are clamped to the domain
+    ///
+    /// output: (u, v, point, distance)
+    ///
+    /// error if newton iteration does not converge
+    fn closest_point(&self, p: &P) -> Result<(f64, f64, P, f64), BasisFunctionError>
+    where
+        P: InnerProductSpace,
+    {
+        surface_closest_point(
+            self.u_basis_function(),
+            self.v_basis_function(),
+            p,
+            |u, v| self.get_point(u, v),
+            |u, v| self.get_ders(2, u, v),
+        )
+    }
 }
 
 pub trait RationalSurface<P>: ParametricSurface<HomoControlPoint<P>>
@@ -77,6 +171,9 @@ where
         .0
     }
 
+    /// get derivatives SKL\[k]\[l] = d^(k+l) S / du^k dv^l at (u, v) for k + l <= der_upper_bond,
+    /// output is (der_upper_bond + 1) x (der_upper_bond + 1),
+    /// entries with k + l > der_upper_bond are zeros
     fn get_ders(&self, der_upper_bond: usize, u: f64, v: f64) -> Vec<Vec<P>> {
         let homo_ders = get_surface_ders(
             self.u_basis_function(),
@@ -87,11 +184,10 @@ where
             v,
         );
 
-        let mut output = vec![vec![P::zeros(); der_upper_bond]; der_upper_bond];
-        output[0][0] = homo_ders[0][0].to_control_point_and_weight().0;
-
+        // NURBS Book A4.4
+        let mut output = vec![vec![P::zeros(); der_upper_bond + 1]; der_upper_bond + 1];
         for k in 0..=der_upper_bond {
-            for l in 0..der_upper_bond - k {
+            for l in 0..=der_upper_bond - k {
                 let mut v = homo_ders[k][l].a();
                 for j in 1..=l {
                     v -= output[k][l - j] * (binomial(l, j) as f64) * homo_ders[0][j].w()
@@ -109,4 +205,44 @@ where
         }
         output
     }
+
+    /// same as `get_point` but never panics
+    ///
+    /// error if knots or control net are invalid, or (u, v) is outside the domain
+    fn try_get_point(&self, u: f64, v: f64) -> Result<P, BasisFunctionError> {
+        self.check_parameter(u, v)?;
+        Ok(self.get_point(u, v))
+    }
+
+    /// same as `get_ders` but never panics
+    ///
+    /// error if knots or control net are invalid, or (u, v) is outside the domain
+    fn try_get_ders(
+        &self,
+        der_upper_bond: usize,
+        u: f64,
+        v: f64,
+    ) -> Result<Vec<Vec<P>>, BasisFunctionError> {
+        self.check_parameter(u, v)?;
+        Ok(self.get_ders(der_upper_bond, u, v))
+    }
+
+    /// closest point on surface to p (NURBS Book 6.1), the nearest of grid sampled points
+    /// is refined by 2D newton iteration, parameters are clamped to the domain
+    ///
+    /// output: (u, v, point, distance)
+    ///
+    /// error if newton iteration does not converge
+    fn closest_point(&self, p: &P) -> Result<(f64, f64, P, f64), BasisFunctionError>
+    where
+        P: InnerProductSpace,
+    {
+        surface_closest_point(
+            self.u_basis_function(),
+            self.v_basis_function(),
+            p,
+            |u, v| self.get_point(u, v),
+            |u, v| self.get_ders(2, u, v),
+        )
+    }
 }
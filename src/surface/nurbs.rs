@@ -1,10 +1,20 @@
 use crate::{
-    basics::{ControlPoint, HomoControlPoint},
-    basis_function::{BsplineBasis, Knots},
+    basics::{
+        algorithm::{
+            check_interval, get_surface_iso_curve_u, get_surface_iso_curve_v, surface_clamp_u,
+            surface_clamp_v, surface_decompose, surface_degree_elevate_u, surface_degree_elevate_v,
+            surface_knot_refine_u, surface_knot_refine_v, surface_split_u, surface_split_v,
+        },
+        ControlPoint, HomoControlPoint,
+    },
+    basis_function::{check_basis, Basis, BasisFunctionError, BernsteinBasis, BsplineBasis, Knots},
+    curve::{check_weights, NurbsCurveBase},
 };
+use std::ops::Range;
 
-use super::{ParametricSurface, RationalSurface};
+use super::{check_control_net, ParametricSurface, RationalBezierSurfaceBase, RationalSurface};
 
+#[derive(Debug, Clone)]
 pub struct NurbsSurfaceBase<P>
 where
     P: ControlPoint,
@@ -32,11 +42,42 @@ where
         }
     }
 
+    /// error if control net is empty or ragged, a weight is not positive,
+    /// knots are not finite and non-decreasing, a degree is too large for control net
+    /// or number of knots != number of control points + degree + 1 in either direction
+    pub fn try_new(
+        control_points: Vec<Vec<HomoControlPoint<P>>>,
+        u_degree: usize,
+        u_knot: Vec<f64>,
+        v_degree: usize,
+        v_knot: Vec<f64>,
+    ) -> Result<Self, BasisFunctionError> {
+        let n_v = check_control_net(&control_points)?;
+        check_weights(control_points.iter().flatten().map(|cp| cp.w()))?;
+        let surface = Self::new(control_points, u_degree, u_knot, v_degree, v_knot);
+        check_basis(&surface.u_basis_function, surface.control_points.len())?;
+        check_basis(&surface.v_basis_function, n_v)?;
+        Ok(surface)
+    }
+
+    /// panic if control net is empty, ragged or too small for degrees, see `try_new_uniform`
     pub fn new_uniform(
         control_points_weights: Vec<Vec<(P, f64)>>,
         u_degree: usize,
         v_degree: usize,
     ) -> Self {
+        Self::try_new_uniform(control_points_weights, u_degree, v_degree).unwrap()
+    }
+
+    /// error if control net is empty or ragged, a weight is not positive,
+    /// or it has less than u_degree + 1 rows or v_degree + 1 columns
+    pub fn try_new_uniform(
+        control_points_weights: Vec<Vec<(P, f64)>>,
+        u_degree: usize,
+        v_degree: usize,
+    ) -> Result<Self, BasisFunctionError> {
+        let n_v = check_control_net(&control_points_weights)?;
+        check_weights(control_points_weights.iter().flatten().map(|(_, w)| *w))?;
         let control_points = control_points_weights
             .iter()
             .map(|v| {
@@ -45,12 +86,248 @@ where
                     .collect::<Vec<_>>()
             })
             .collect::<Vec<_>>();
+        Ok(Self {
+            u_basis_function: BsplineBasis::new_uniform(u_degree, control_points.len())?,
+            v_basis_function: BsplineBasis::new_uniform(v_degree, n_v)?,
+            control_points,
+        })
+    }
+
+    /// insert all knots in x at once into u knot vector without changing the shape of surface,
+    /// x should be sorted in non-decreasing order
+    ///
+    /// knots are inserted on homogeneous control points
+    pub fn refine_knots_u(&self, x: &[f64]) -> Result<Self, BasisFunctionError> {
+        let p = self.p();
+        let (knots, control_points) =
+            surface_knot_refine_u(p, self.u_basis_function.knots(), &self.control_points, x)?;
+        Ok(Self {
+            u_basis_function: BsplineBasis::new(p, knots),
+            v_basis_function: self.v_basis_function.clone(),
+            control_points,
+        })
+    }
+
+    /// insert all knots in x at once into v knot vector without changing the shape of surface,
+    /// x should be sorted in non-decreasing order
+    ///
+    /// knots are inserted on homogeneous control points
+    pub fn refine_knots_v(&self, x: &[f64]) -> Result<Self, BasisFunctionError> {
+        let q = self.q();
+        let (knots, control_points) =
+            surface_knot_refine_v(q, self.v_basis_function.knots(), &self.control_points, x)?;
+        Ok(Self {
+            u_basis_function: self.u_basis_function.clone(),
+            v_basis_function: BsplineBasis::new(q, knots),
+            control_points,
+        })
+    }
+
+    /// raise degree in u direction from p to p + t without changing the shape of surface,
+    /// a surface unclamped in u direction is clamped first
+    ///
+    /// degree elevation is done on homogeneous control points
+    pub fn elevate_degree_u(&self, t: usize) -> Self {
+        let p = self.p();
+        if !self.u_basis_function.knots().is_clamped(p) {
+            return self.clamp_u().elevate_degree_u(t);
+        }
+        let (knots, control_points) =
+            surface_degree_elevate_u(p, self.u_basis_function.knots(), &self.control_points, t);
+        Self {
+            u_basis_function: BsplineBasis::new(p + t, knots),
+            v_basis_function: self.v_basis_function.clone(),
+            control_points,
+        }
+    }
+
+    /// raise degree in v direction from q to q + t without changing the shape of surface,
+    /// a surface unclamped in v direction is clamped first
+    ///
+    /// degree elevation is done on homogeneous control points
+    pub fn elevate_degree_v(&self, t: usize) -> Self {
+        let q = self.q();
+        if !self.v_basis_function.knots().is_clamped(q) {
+            return self.clamp_v().elevate_degree_v(t);
+        }
+        let (knots, control_points) =
+            surface_degree_elevate_v(q, self.v_basis_function.knots(), &self.control_points, t);
+        Self {
+            u_basis_function: self.u_basis_function.clone(),
+            v_basis_function: BsplineBasis::new(q + t, knots),
+            control_points,
+        }
+    }
+
+    /// decompose surface into rational bezier patches, indexed by \[u segment]\[v segment],
+    /// the patch (i, j) is on the i-th nonempty u knot span x the j-th nonempty v knot span,
+    /// a surface unclamped in either direction is clamped first
+    pub fn decompose(&self) -> Vec<Vec<RationalBezierSurfaceBase<P>>> {
+        if !self.u_basis_function.knots().is_clamped(self.p()) {
+            return self.clamp_u().decompose();
+        }
+        if !self.v_basis_function.knots().is_clamped(self.q()) {
+            return self.clamp_v().decompose();
+        }
+        let u_breakpoints = self.u_basis_function.knots().breakpoints(self.p());
+        let v_breakpoints = self.v_basis_function.knots().breakpoints(self.q());
+        surface_decompose(
+            self.p(),
+            self.u_basis_function.knots(),
+            self.q(),
+            self.v_basis_function.knots(),
+            &self.control_points,
+        )
+        .into_iter()
+        .zip(u_breakpoints.windows(2))
+        .map(|(strip, u)| {
+            strip
+                .into_iter()
+                .zip(v_breakpoints.windows(2))
+                .map(|(patch, v)| {
+                    RationalBezierSurfaceBase::from_basis(
+                        BernsteinBasis::with_len_on(patch.len(), u[0], u[1]),
+                        BernsteinBasis::with_len_on(patch[0].len(), v[0], v[1]),
+                        patch,
+                    )
+                })
+                .collect()
+        })
+        .collect()
+    }
+
+    /// split surface at u into two surfaces on \[u_p, u] and \[u, u_m-p] in u direction
+    ///
+    /// error if u is not inside the domain of surface
+    pub fn split_u(&self, u: f64) -> Result<(Self, Self), BasisFunctionError> {
+        let p = self.p();
+        let ((left_knots, left), (right_knots, right)) =
+            surface_split_u(p, self.u_basis_function.knots(), &self.control_points, u)?;
+        Ok((
+            Self {
+                u_basis_function: BsplineBasis::new(p, left_knots),
+                v_basis_function: self.v_basis_function.clone(),
+                control_points: left,
+            },
+            Self {
+                u_basis_function: BsplineBasis::new(p, right_knots),
+                v_basis_function: self.v_basis_function.clone(),
+                control_points: right,
+            },
+        ))
+    }
+
+    /// split surface at v into two surfaces on \[v_q, v] and \[v, v_m-q] in v direction
+    ///
+    /// error if v is not inside the domain of surface
+    pub fn split_v(&self, v: f64) -> Result<(Self, Self), BasisFunctionError> {
+        let q = self.q();
+        let ((left_knots, left), (right_knots, right)) =
+            surface_split_v(q, self.v_basis_function.knots(), &self.control_points, v)?;
+        Ok((
+            Self {
+                u_basis_function: self.u_basis_function.clone(),
+                v_basis_function: BsplineBasis::new(q, left_knots),
+                control_points: left,
+            },
+            Self {
+                u_basis_function: self.u_basis_function.clone(),
+                v_basis_function: BsplineBasis::new(q, right_knots),
+                control_points: right,
+            },
+        ))
+    }
+
+    /// extract the part of surface on u x v
+    ///
+    /// error if u or v is not a valid interval inside the domain of surface
+    pub fn sub_surface(&self, u: Range<f64>, v: Range<f64>) -> Result<Self, BasisFunctionError> {
+        let ((u_start, u_end), (v_start, v_end)) = self.domain();
+        check_interval(&u, u_start, u_end)?;
+        check_interval(&v, v_start, v_end)?;
+        let mut surface = self.clone();
+        if u.start > u_start {
+            surface = surface.split_u(u.start)?.1;
+        }
+        if u.end < u_end {
+            surface = surface.split_u(u.end)?.0;
+        }
+        if v.start > v_start {
+            surface = surface.split_v(v.start)?.1;
+        }
+        if v.end < v_end {
+            surface = surface.split_v(v.end)?.0;
+        }
+        Ok(surface)
+    }
+
+    /// same surface on domain u x v, knots are mapped affinely
+    ///
+    /// error if u or v is empty or not finite
+    pub fn reparametrize(&self, u: Range<f64>, v: Range<f64>) -> Result<Self, BasisFunctionError> {
+        let (p, q) = (self.p(), self.q());
+        let u_knots = self
+            .u_basis_function
+            .knots()
+            .reparametrize(p, u.start, u.end)?;
+        let v_knots = self
+            .v_basis_function
+            .knots()
+            .reparametrize(q, v.start, v.end)?;
+        Ok(Self {
+            u_basis_function: BsplineBasis::new(p, u_knots),
+            v_basis_function: BsplineBasis::new(q, v_knots),
+            control_points: self.control_points.clone(),
+        })
+    }
+
+    /// same surface on clamped knots in u direction, domain is unchanged
+    pub fn clamp_u(&self) -> Self {
+        let p = self.p();
+        let (knots, control_points) =
+            surface_clamp_u(p, self.u_basis_function.knots(), &self.control_points);
         Self {
-            u_basis_function: BsplineBasis::new_uniform(u_degree, control_points.len()).unwrap(),
-            v_basis_function: BsplineBasis::new_uniform(v_degree, control_points[0].len()).unwrap(),
+            u_basis_function: BsplineBasis::new(p, knots),
+            v_basis_function: self.v_basis_function.clone(),
             control_points,
         }
     }
+
+    /// same surface on clamped knots in v direction, domain is unchanged
+    pub fn clamp_v(&self) -> Self {
+        let q = self.q();
+        let (knots, control_points) =
+            surface_clamp_v(q, self.v_basis_function.knots(), &self.control_points);
+        Self {
+            u_basis_function: self.u_basis_function.clone(),
+            v_basis_function: BsplineBasis::new(q, knots),
+            control_points,
+        }
+    }
+
+    /// same surface with u direction reversed on the same domain
+    pub fn reverse_u(&self) -> Self {
+        let p = self.p();
+        Self {
+            u_basis_function: BsplineBasis::new(p, self.u_basis_function.knots().reverse(p)),
+            v_basis_function: self.v_basis_function.clone(),
+            control_points: self.control_points.iter().rev().cloned().collect(),
+        }
+    }
+
+    /// same surface with v direction reversed on the same domain
+    pub fn reverse_v(&self) -> Self {
+        let q = self.q();
+        Self {
+            u_basis_function: self.u_basis_function.clone(),
+            v_basis_function: BsplineBasis::new(q, self.v_basis_function.knots().reverse(q)),
+            control_points: self
+                .control_points
+                .iter()
+                .map(|row| row.iter().rev().copied().collect())
+                .collect(),
+        }
+    }
 }
 
 impl<P> ParametricSurface<HomoControlPoint<P>> for NurbsSurfaceBase<P>
@@ -58,6 +335,7 @@ where
     P: ControlPoint,
 {
     type BasisFunction = BsplineBasis;
+    type IsoCurve = NurbsCurveBase<P>;
 
     fn u_basis_function(&self) -> &Self::BasisFunction {
         &self.u_basis_function
@@ -70,6 +348,20 @@ where
     fn control_points(&self) -> &Vec<Vec<HomoControlPoint<P>>> {
         &self.control_points
     }
+
+    fn iso_curve_u(&self, u: f64) -> Result<Self::IsoCurve, BasisFunctionError> {
+        Ok(NurbsCurveBase::from_basis(
+            self.v_basis_function.clone(),
+            get_surface_iso_curve_u(&self.u_basis_function, &self.control_points, u)?,
+        ))
+    }
+
+    fn iso_curve_v(&self, v: f64) -> Result<Self::IsoCurve, BasisFunctionError> {
+        Ok(NurbsCurveBase::from_basis(
+            self.u_basis_function.clone(),
+            get_surface_iso_curve_v(&self.v_basis_function, &self.control_points, v)?,
+        ))
+    }
 }
 
 impl<P> RationalSurface<P> for NurbsSurfaceBase<P> where P: ControlPoint {}
@@ -0,0 +1,255 @@
+use crate::{
+    basics::{
+        algorithm::{
+            bezier_surface_degree_elevate_u, bezier_surface_degree_elevate_v,
+            bezier_surface_split_u, bezier_surface_split_v, check_interval, de_casteljaul_surface,
+            get_surface_iso_curve_u, get_surface_iso_curve_v,
+        },
+        ControlPoint, HomoControlPoint,
+    },
+    basis_function::{Basis, BasisFunctionError, BernsteinBasis},
+    curve::{check_weights, RationalBezierCurveBase},
+};
+use std::ops::Range;
+
+use super::{check_control_net, ParametricSurface, RationalSurface};
+
+#[derive(Debug, Clone)]
+pub struct RationalBezierSurfaceBase<P>
+where
+    P: ControlPoint,
+{
+    u_basis_function: BernsteinBasis,
+    v_basis_function: BernsteinBasis,
+    control_points: Vec<Vec<HomoControlPoint<P>>>,
+}
+
+impl<P> RationalBezierSurfaceBase<P>
+where
+    P: ControlPoint,
+{
+    pub fn new(control_points_weights: Vec<Vec<(P, f64)>>) -> Self {
+        let control_points = control_points_weights
+            .iter()
+            .map(|v| {
+                v.iter()
+                    .map(|(p, w)| HomoControlPoint::<P>::from_control_point(*p, *w))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        Self::from_homo_control_points(control_points)
+    }
+
+    /// error if control_points_weights is empty, its rows differ in length
+    /// or a weight is not positive
+    pub fn try_new(control_points_weights: Vec<Vec<(P, f64)>>) -> Result<Self, BasisFunctionError> {
+        check_control_net(&control_points_weights)?;
+        check_weights(control_points_weights.iter().flatten().map(|(_, w)| *w))?;
+        Ok(Self::new(control_points_weights))
+    }
+
+    pub(crate) fn from_homo_control_points(control_points: Vec<Vec<HomoControlPoint<P>>>) -> Self {
+        Self {
+            u_basis_function: BernsteinBasis::new(control_points.len()),
+            v_basis_function: BernsteinBasis::new(control_points[0].len()),
+            control_points,
+        }
+    }
+
+    pub(crate) fn from_basis(
+        u_basis_function: BernsteinBasis,
+        v_basis_function: BernsteinBasis,
+        control_points: Vec<Vec<HomoControlPoint<P>>>,
+    ) -> Self {
+        Self {
+            u_basis_function,
+            v_basis_function,
+            control_points,
+        }
+    }
+
+    /// surface on domain u x v instead of \[0, 1] x \[0, 1]
+    ///
+    /// error if control_points_weights is empty or ragged, a weight is not positive,
+    /// or u or v is empty or not finite
+    pub fn new_on(
+        control_points_weights: Vec<Vec<(P, f64)>>,
+        u: Range<f64>,
+        v: Range<f64>,
+    ) -> Result<Self, BasisFunctionError> {
+        Self::try_new(control_points_weights)?.reparametrize(u, v)
+    }
+
+    /// surface on the same domain with other homogeneous control points
+    fn with_homo_control_points(&self, control_points: Vec<Vec<HomoControlPoint<P>>>) -> Self {
+        Self {
+            u_basis_function: self.u_basis_function.with_len(control_points.len()),
+            v_basis_function: self.v_basis_function.with_len(control_points[0].len()),
+            control_points,
+        }
+    }
+
+    /// raise degree in u direction from p to p + t without changing the shape of surface,
+    /// degree elevation is done on homogeneous control points
+    pub fn elevate_degree_u(&self, t: usize) -> Self {
+        self.with_homo_control_points(bezier_surface_degree_elevate_u(&self.control_points, t))
+    }
+
+    /// raise degree in v direction from q to q + t without changing the shape of surface,
+    /// degree elevation is done on homogeneous control points
+    pub fn elevate_degree_v(&self, t: usize) -> Self {
+        self.with_homo_control_points(bezier_surface_degree_elevate_v(&self.control_points, t))
+    }
+
+    /// split surface at u into two surfaces on \[a, u] and \[u, b] in u direction,
+    /// \[a, b] is the u domain of surface
+    ///
+    /// error if u is not inside the u domain
+    pub fn split_u(&self, u: f64) -> Result<(Self, Self), BasisFunctionError> {
+        let (a, b) = self.u_basis_function.domain();
+        if u <= a {
+            return Err(BasisFunctionError::ULessThanMin);
+        }
+        if u >= b {
+            return Err(BasisFunctionError::UGreaterThanMax);
+        }
+        let t = self.u_basis_function.to_unit(u);
+        let (left, right) = bezier_surface_split_u(&self.control_points, t);
+        Ok((
+            Self {
+                u_basis_function: BernsteinBasis::with_len_on(left.len(), a, u),
+                v_basis_function: self.v_basis_function.clone(),
+                control_points: left,
+            },
+            Self {
+                u_basis_function: BernsteinBasis::with_len_on(right.len(), u, b),
+                v_basis_function: self.v_basis_function.clone(),
+                control_points: right,
+            },
+        ))
+    }
+
+    /// split surface at v into two surfaces on \[a, v] and \[v, b] in v direction,
+    /// \[a, b] is the v domain of surface
+    ///
+    /// error if v is not inside the v domain
+    pub fn split_v(&self, v: f64) -> Result<(Self, Self), BasisFunctionError> {
+        let (a, b) = self.v_basis_function.domain();
+        if v <= a {
+            return Err(BasisFunctionError::ULessThanMin);
+        }
+        if v >= b {
+            return Err(BasisFunctionError::UGreaterThanMax);
+        }
+        let t = self.v_basis_function.to_unit(v);
+        let (left, right) = bezier_surface_split_v(&self.control_points, t);
+        Ok((
+            Self {
+                u_basis_function: self.u_basis_function.clone(),
+                v_basis_function: BernsteinBasis::with_len_on(left[0].len(), a, v),
+                control_points: left,
+            },
+            Self {
+                u_basis_function: self.u_basis_function.clone(),
+                v_basis_function: BernsteinBasis::with_len_on(right[0].len(), v, b),
+                control_points: right,
+            },
+        ))
+    }
+
+    /// extract the part of surface on u x v
+    ///
+    /// error if u or v is not a valid interval inside the domain
+    pub fn sub_surface(&self, u: Range<f64>, v: Range<f64>) -> Result<Self, BasisFunctionError> {
+        let ((ua, ub), (va, vb)) = self.domain();
+        check_interval(&u, ua, ub)?;
+        check_interval(&v, va, vb)?;
+        let mut surface = self.clone();
+        if u.end < ub {
+            surface = surface.split_u(u.end)?.0;
+        }
+        if u.start > ua {
+            surface = surface.split_u(u.start)?.1;
+        }
+        if v.end < vb {
+            surface = surface.split_v(v.end)?.0;
+        }
+        if v.start > va {
+            surface = surface.split_v(v.start)?.1;
+        }
+        Ok(surface)
+    }
+
+    /// same surface on domain u x v
+    ///
+    /// error if u or v is empty or not finite
+    pub fn reparametrize(&self, u: Range<f64>, v: Range<f64>) -> Result<Self, BasisFunctionError> {
+        Ok(Self {
+            u_basis_function: BernsteinBasis::new_on(self.control_points.len(), u.start, u.end)?,
+            v_basis_function: BernsteinBasis::new_on(self.control_points[0].len(), v.start, v.end)?,
+            control_points: self.control_points.clone(),
+        })
+    }
+
+    /// same surface with u direction reversed on the same domain
+    pub fn reverse_u(&self) -> Self {
+        self.with_homo_control_points(self.control_points.iter().rev().cloned().collect())
+    }
+
+    /// same surface with v direction reversed on the same domain
+    pub fn reverse_v(&self) -> Self {
+        self.with_homo_control_points(
+            self.control_points
+                .iter()
+                .map(|row| row.iter().rev().copied().collect())
+                .collect(),
+        )
+    }
+}
+
+impl<P: ControlPoint> ParametricSurface<HomoControlPoint<P>> for RationalBezierSurfaceBase<P> {
+    type BasisFunction = BernsteinBasis;
+    type IsoCurve = RationalBezierCurveBase<P>;
+
+    fn u_basis_function(&self) -> &Self::BasisFunction {
+        &self.u_basis_function
+    }
+
+    fn v_basis_function(&self) -> &Self::BasisFunction {
+        &self.v_basis_function
+    }
+
+    fn control_points(&self) -> &Vec<Vec<HomoControlPoint<P>>> {
+        &self.control_points
+    }
+
+    fn iso_curve_u(&self, u: f64) -> Result<Self::IsoCurve, BasisFunctionError> {
+        Ok(RationalBezierCurveBase::from_basis(
+            self.v_basis_function.clone(),
+            get_surface_iso_curve_u(&self.u_basis_function, &self.control_points, u)?,
+        ))
+    }
+
+    fn iso_curve_v(&self, v: f64) -> Result<Self::IsoCurve, BasisFunctionError> {
+        Ok(RationalBezierCurveBase::from_basis(
+            self.u_basis_function.clone(),
+            get_surface_iso_curve_v(&self.v_basis_function, &self.control_points, v)?,
+        ))
+    }
+}
+
+impl<P> RationalSurface<P> for RationalBezierSurfaceBase<P>
+where
+    P: ControlPoint,
+{
+    /// use deCasteljaul algorithm on homogeneous control points
+    fn get_point(&self, u: f64, v: f64) -> P {
+        de_casteljaul_surface(
+            &self.control_points,
+            self.u_basis_function.to_unit(u),
+            self.v_basis_function.to_unit(v),
+        )
+        .to_control_point_and_weight()
+        .0
+    }
+}